@@ -208,6 +208,73 @@ pub const ACHIEVEMENTS: &[Achievement] = &[
         icon: "🎯",
     },
 
+    // ===== DEEP WORK ACHIEVEMENTS (Timewarrior) =====
+    Achievement {
+        id: "deep_focus",
+        title: "Deep Focus",
+        description: "Track 2 hours of focus time in one day",
+        tier: AchievementTier::Common,
+        icon: "🎧",
+    },
+    Achievement {
+        id: "flow_state",
+        title: "Flow State",
+        description: "Track 90 minutes without a break",
+        tier: AchievementTier::Uncommon,
+        icon: "🌊",
+    },
+    Achievement {
+        id: "deep_diver",
+        title: "Deep Diver",
+        description: "Track 4 hours of focus time in one day",
+        tier: AchievementTier::Uncommon,
+        icon: "🤿",
+    },
+    Achievement {
+        id: "monk_mode",
+        title: "Monk Mode",
+        description: "Track 6 hours of focus time in one day",
+        tier: AchievementTier::Rare,
+        icon: "🧘",
+    },
+    Achievement {
+        id: "deep_work_habit",
+        title: "Deep Work Habit",
+        description: "Track 4 hours of focus time on 20 different days",
+        tier: AchievementTier::Epic,
+        icon: "🏔️",
+    },
+
+    // ===== FOCUS SESSION ACHIEVEMENTS =====
+    Achievement {
+        id: "first_focus",
+        title: "First Focus",
+        description: "Complete a focus session",
+        tier: AchievementTier::Common,
+        icon: "🍅",
+    },
+    Achievement {
+        id: "pomodoro_pro",
+        title: "Pomodoro Pro",
+        description: "Complete 50 focus sessions",
+        tier: AchievementTier::Uncommon,
+        icon: "⏱️",
+    },
+    Achievement {
+        id: "focus_marathon",
+        title: "Focus Marathon",
+        description: "Complete 8 focus sessions in one day",
+        tier: AchievementTier::Rare,
+        icon: "🏃",
+    },
+    Achievement {
+        id: "hundred_hours",
+        title: "Hundred Hours",
+        description: "Spend 100 hours in focus sessions",
+        tier: AchievementTier::Epic,
+        icon: "⌛",
+    },
+
     // ===== POWER ACHIEVEMENTS =====
     Achievement {
         id: "strength_incarnate",
@@ -1,5 +1,7 @@
 pub mod definitions;
 pub mod tracker;
+pub mod titles;
 
 pub use definitions::{Achievement, AchievementTier};
-pub use tracker::AchievementTracker;
+pub use tracker::{AchievementTracker, MissedDays};
+pub use titles::Title;
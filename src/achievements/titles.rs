@@ -0,0 +1,93 @@
+/// What a character has to do to earn a title
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TitleUnlock {
+    Achievement(&'static str), // Achievement ID
+    Quests(u32),               // Total quests completed
+    Prestige(u32),             // Prestige rank reached
+}
+
+#[derive(Debug, Clone)]
+pub struct Title {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub unlock: TitleUnlock,
+}
+
+pub const TITLES: &[Title] = &[
+    // ===== ACHIEVEMENT TITLES =====
+    Title { id: "novice", name: "the Novice", unlock: TitleUnlock::Achievement("first_steps") },
+    Title { id: "undaunted", name: "the Undaunted", unlock: TitleUnlock::Achievement("the_undaunted") },
+    Title { id: "phoenix", name: "Phoenix", unlock: TitleUnlock::Achievement("phoenix_rising") },
+    Title { id: "balanced", name: "the Balanced", unlock: TitleUnlock::Achievement("master_of_balance") },
+    Title { id: "tireless", name: "the Tireless", unlock: TitleUnlock::Achievement("marathon_hero") },
+    Title { id: "renaissance", name: "Renaissance Soul", unlock: TitleUnlock::Achievement("renaissance_soul") },
+    Title { id: "hoarder", name: "Dragon of the Hoard", unlock: TitleUnlock::Achievement("gold_hoarder") },
+    Title { id: "early_bird", name: "the Early Bird", unlock: TitleUnlock::Achievement("early_riser") },
+    Title { id: "punctual", name: "the Punctual", unlock: TitleUnlock::Achievement("punctual_perfectionist") },
+    Title { id: "legendary", name: "the Legendary", unlock: TitleUnlock::Achievement("legendary_warrior") },
+    Title { id: "living_legend", name: "Living Legend", unlock: TitleUnlock::Achievement("living_legend") },
+    Title { id: "completionist", name: "the Completionist", unlock: TitleUnlock::Achievement("completionist") },
+    Title { id: "transcendent", name: "the Transcendent", unlock: TitleUnlock::Achievement("transcendent_power") },
+
+    // ===== MILESTONE TITLES =====
+    Title { id: "diligent", name: "the Diligent", unlock: TitleUnlock::Quests(50) },
+    Title { id: "relentless", name: "the Relentless", unlock: TitleUnlock::Quests(250) },
+    Title { id: "reborn", name: "the Reborn", unlock: TitleUnlock::Prestige(1) },
+    Title { id: "eternal", name: "the Eternal", unlock: TitleUnlock::Prestige(5) },
+];
+
+impl Title {
+    pub fn all() -> &'static [Title] {
+        TITLES
+    }
+
+    /// Find a title by ID or display name (case-insensitive)
+    pub fn find(query: &str) -> Option<&'static Title> {
+        let query = query.to_lowercase();
+        TITLES.iter()
+            .find(|t| t.id == query || t.name.to_lowercase() == query)
+    }
+
+    /// Human-readable unlock condition
+    pub fn requirement(&self) -> String {
+        match self.unlock {
+            TitleUnlock::Achievement(id) => {
+                let title = crate::achievements::Achievement::get_by_id(id)
+                    .map(|a| a.title)
+                    .unwrap_or(id);
+                format!("Achievement: {}", title)
+            }
+            TitleUnlock::Quests(count) => format!("Complete {} quests", count),
+            TitleUnlock::Prestige(rank) => format!("Reach prestige rank {}", rank),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::achievements::definitions::ACHIEVEMENTS;
+
+    #[test]
+    fn test_title_ids_unique() {
+        for (i, title) in TITLES.iter().enumerate() {
+            assert!(TITLES[i + 1..].iter().all(|t| t.id != title.id), "duplicate title {}", title.id);
+        }
+    }
+
+    #[test]
+    fn test_achievement_titles_reference_real_achievements() {
+        for title in TITLES {
+            if let TitleUnlock::Achievement(id) = title.unlock {
+                assert!(ACHIEVEMENTS.iter().any(|a| a.id == id), "unknown achievement {}", id);
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_title() {
+        assert_eq!(Title::find("undaunted").unwrap().name, "the Undaunted");
+        assert_eq!(Title::find("The Undaunted").unwrap().id, "undaunted");
+        assert!(Title::find("the Nobody").is_none());
+    }
+}
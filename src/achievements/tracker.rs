@@ -2,12 +2,14 @@ use serde::{Deserialize, Serialize};
 use anyhow::{Result, Context};
 use std::path::Path;
 use std::collections::{HashMap, HashSet};
-use chrono::{Utc, NaiveDate};
+use chrono::NaiveDate;
 use crate::achievements::definitions::{Achievement, ACHIEVEMENTS};
-use crate::character::Character;
+use crate::achievements::titles::{Title, TitleUnlock, TITLES};
+use crate::character::{Character, RestDays};
 use crate::progression::LootDrop;
+use crate::timewarrior::{DailyFocus, DEEP_WORK_MINUTES};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AchievementProgress {
     // Tracking for various achievement conditions
     pub quests_completed: u32,
@@ -26,37 +28,45 @@ pub struct AchievementProgress {
     pub comeback_quests_after_break: u32, // Track if on comeback
     pub had_30_day_break: bool,
     pub highest_stat_value: u16,
+    #[serde(default)]
+    pub current_streak: u32, // Consecutive active days
+    #[serde(default)]
+    pub longest_streak: u32,
+    #[serde(default)]
+    pub best_focus_minutes: u32, // Most time tracked in one day
+    #[serde(default)]
+    pub longest_focus_block: u32, // Minutes
+    #[serde(default)]
+    pub deep_work_days: HashSet<String>, // Dates with 4+ hours tracked
+    #[serde(default)]
+    pub sessions_completed: u32, // Focus sessions run to the end
+    #[serde(default)]
+    pub session_minutes: u32, // All focus session time, partial included
+    #[serde(default)]
+    pub session_day: Option<String>, // ISO date of the latest completed session
+    #[serde(default)]
+    pub session_day_count: u32, // Completed sessions on that day
+    #[serde(default)]
+    pub most_sessions_in_day: u32,
+}
+
+/// What counts as a missed day for the daily streak and long breaks
+#[derive(Debug, Clone, Copy)]
+pub struct MissedDays<'a> {
+    /// Missed days the streak survives (from WIS)
+    pub grace_days: u32,
+    /// Days that are never missed
+    pub rest: &'a RestDays,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AchievementTracker {
+    #[serde(default)]
+    pub schema_version: u32,
     pub unlocked: HashSet<String>, // Achievement IDs that have been unlocked
     pub progress: AchievementProgress,
 }
 
-impl Default for AchievementProgress {
-    fn default() -> Self {
-        Self {
-            quests_completed: 0,
-            quests_by_difficulty: HashMap::new(),
-            difficulty_10_quests: 0,
-            active_days: HashSet::new(),
-            projects_completed: HashSet::new(),
-            epic_loot_received: false,
-            loot_drops_received: 0,
-            rewards_purchased: 0,
-            early_tasks: 0,
-            grace_period_tasks: 0,
-            on_time_tasks: 0,
-            tasks_with_due_date: 0,
-            last_activity_date: None,
-            comeback_quests_after_break: 0,
-            had_30_day_break: false,
-            highest_stat_value: 0,
-        }
-    }
-}
-
 impl Default for AchievementTracker {
     fn default() -> Self {
         Self::new()
@@ -66,6 +76,7 @@ impl Default for AchievementTracker {
 impl AchievementTracker {
     pub fn new() -> Self {
         Self {
+            schema_version: <Self as crate::storage::Versioned>::SCHEMA_VERSION,
             unlocked: HashSet::new(),
             progress: AchievementProgress::default(),
         }
@@ -76,11 +87,8 @@ impl AchievementTracker {
             return Ok(Self::new());
         }
 
-        let file = std::fs::File::open(path)
-            .context("Failed to open achievements file")?;
-        let tracker: AchievementTracker = serde_json::from_reader(file)
-            .context("Failed to parse achievements JSON")?;
-        Ok(tracker)
+        crate::storage::load_with_backup(path)
+            .context("Failed to load achievements")
     }
 
     pub fn save(&self, path: &Path) -> Result<()> {
@@ -94,8 +102,10 @@ impl AchievementTracker {
         difficulty: u8,
         timing: TaskTiming,
         project: Option<&str>,
+        completed_on: NaiveDate,
+        missed: MissedDays,
     ) -> Vec<&'static Achievement> {
-        let today = Utc::now().format("%Y-%m-%d").to_string();
+        let today = completed_on.format("%Y-%m-%d").to_string();
 
         // Update progress
         self.progress.quests_completed += 1;
@@ -108,22 +118,30 @@ impl AchievementTracker {
         // Track active days
         self.progress.active_days.insert(today.clone());
 
-        // Check for comeback scenario
-        if let Some(ref last_date) = self.progress.last_activity_date {
-            if let Ok(last) = NaiveDate::parse_from_str(last_date, "%Y-%m-%d") {
-                if let Ok(current) = NaiveDate::parse_from_str(&today, "%Y-%m-%d") {
-                    let days_diff = (current - last).num_days();
-                    if days_diff >= 30 {
-                        self.progress.had_30_day_break = true;
-                        self.progress.comeback_quests_after_break = 0;
-                    }
-                    if self.progress.had_30_day_break {
-                        self.progress.comeback_quests_after_break += 1;
-                    }
+        // Check for comeback scenario. Quests credited late (older than the
+        // last activity) count toward totals but don't move the streak.
+        // Rest days in between are neither missed days nor part of a break.
+        let last = self.progress.last_activity_date.as_deref()
+            .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+        match last {
+            Some(last) if completed_on < last => {}
+            Some(last) => {
+                let days_diff = missed.rest.active_days_between(last, completed_on);
+                if days_diff >= 30 {
+                    self.progress.had_30_day_break = true;
+                    self.progress.comeback_quests_after_break = 0;
+                }
+                if self.progress.had_30_day_break {
+                    self.progress.comeback_quests_after_break += 1;
                 }
+                self.update_streak(days_diff, missed.grace_days);
+                self.progress.last_activity_date = Some(today);
+            }
+            None => {
+                self.update_streak(1, missed.grace_days);
+                self.progress.last_activity_date = Some(today);
             }
         }
-        self.progress.last_activity_date = Some(today);
 
         // Track projects
         if let Some(proj) = project {
@@ -160,6 +178,51 @@ impl AchievementTracker {
         self.check_achievements(character)
     }
 
+    /// Advance or reset the daily streak given the days since the last activity.
+    /// Up to `grace_days` missed days (from WIS) don't break the streak.
+    fn update_streak(&mut self, days_since_last: i64, grace_days: u32) {
+        if days_since_last <= 0 {
+            self.progress.current_streak = self.progress.current_streak.max(1);
+        } else if days_since_last <= 1 + grace_days as i64 {
+            self.progress.current_streak += 1;
+        } else {
+            self.progress.current_streak = 1;
+        }
+
+        self.progress.longest_streak = self.progress.longest_streak.max(self.progress.current_streak);
+    }
+
+    /// Record a day's tracked focus time (it only ever raises the records)
+    pub fn record_focus_day(&mut self, day: NaiveDate, focus: &DailyFocus, character: &Character) -> Vec<&'static Achievement> {
+        self.progress.best_focus_minutes = self.progress.best_focus_minutes.max(focus.minutes);
+        self.progress.longest_focus_block = self.progress.longest_focus_block.max(focus.longest_block);
+        if focus.minutes >= DEEP_WORK_MINUTES {
+            self.progress.deep_work_days.insert(day.format("%Y-%m-%d").to_string());
+        }
+
+        self.check_achievements(character)
+    }
+
+    /// Record a focus session; only completed sessions count toward the
+    /// session totals
+    pub fn record_focus_session(&mut self, minutes: u32, completed: bool, day: NaiveDate, character: &Character) -> Vec<&'static Achievement> {
+        self.progress.session_minutes += minutes;
+
+        if completed {
+            let day = day.format("%Y-%m-%d").to_string();
+            self.progress.sessions_completed += 1;
+            if self.progress.session_day.as_ref() == Some(&day) {
+                self.progress.session_day_count += 1;
+            } else {
+                self.progress.session_day = Some(day);
+                self.progress.session_day_count = 1;
+            }
+            self.progress.most_sessions_in_day = self.progress.most_sessions_in_day.max(self.progress.session_day_count);
+        }
+
+        self.check_achievements(character)
+    }
+
     /// Record loot drop received
     pub fn record_loot_drop(&mut self, loot: &LootDrop, character: &Character) -> Vec<&'static Achievement> {
         self.progress.loot_drops_received += 1;
@@ -246,6 +309,19 @@ impl AchievementTracker {
             "time_master" => self.progress.tasks_with_due_date >= 100,
             "punctual_perfectionist" => self.progress.on_time_tasks >= 100,
 
+            // Deep work
+            "deep_focus" => self.progress.best_focus_minutes >= 120,
+            "flow_state" => self.progress.longest_focus_block >= 90,
+            "deep_diver" => self.progress.best_focus_minutes >= DEEP_WORK_MINUTES,
+            "monk_mode" => self.progress.best_focus_minutes >= 360,
+            "deep_work_habit" => self.progress.deep_work_days.len() >= 20,
+
+            // Focus sessions
+            "first_focus" => self.progress.sessions_completed >= 1,
+            "pomodoro_pro" => self.progress.sessions_completed >= 50,
+            "focus_marathon" => self.progress.most_sessions_in_day >= 8,
+            "hundred_hours" => self.progress.session_minutes >= 6000,
+
             // Power achievements (stat-based)
             "strength_incarnate" => character.stats.strength >= 100.0,
             "lightning_reflexes" => character.stats.dexterity >= 100.0,
@@ -303,15 +379,11 @@ impl AchievementTracker {
             "marathon_hero" => (self.progress.active_days.len().min(100) as f64) / 100.0,
             "renaissance_soul" => (self.progress.projects_completed.len().min(10) as f64) / 10.0,
 
-            "phoenix_rising" => {
-                if self.progress.had_30_day_break {
-                    (self.progress.comeback_quests_after_break.min(5) as f64) / 5.0
-                } else {
-                    0.0
-                }
+            "phoenix_rising" if self.progress.had_30_day_break => {
+                (self.progress.comeback_quests_after_break.min(5) as f64) / 5.0
             }
 
-            "epic_collector" => if self.progress.epic_loot_received { 1.0 } else { 0.0 },
+            "epic_collector" if self.progress.epic_loot_received => 1.0,
             "gold_hoarder" => (character.gold.min(5000) as f64) / 5000.0,
             "wise_spender" => (self.progress.rewards_purchased.min(10) as f64) / 10.0,
             "treasure_hunter" => (self.progress.loot_drops_received.min(50) as f64) / 50.0,
@@ -321,6 +393,17 @@ impl AchievementTracker {
             "time_master" => (self.progress.tasks_with_due_date.min(100) as f64) / 100.0,
             "punctual_perfectionist" => (self.progress.on_time_tasks.min(100) as f64) / 100.0,
 
+            "deep_focus" => (self.progress.best_focus_minutes.min(120) as f64) / 120.0,
+            "flow_state" => (self.progress.longest_focus_block.min(90) as f64) / 90.0,
+            "deep_diver" => (self.progress.best_focus_minutes.min(DEEP_WORK_MINUTES) as f64) / DEEP_WORK_MINUTES as f64,
+            "monk_mode" => (self.progress.best_focus_minutes.min(360) as f64) / 360.0,
+            "deep_work_habit" => (self.progress.deep_work_days.len().min(20) as f64) / 20.0,
+
+            "first_focus" => (self.progress.sessions_completed.min(1) as f64) / 1.0,
+            "pomodoro_pro" => (self.progress.sessions_completed.min(50) as f64) / 50.0,
+            "focus_marathon" => (self.progress.most_sessions_in_day.min(8) as f64) / 8.0,
+            "hundred_hours" => (self.progress.session_minutes.min(6000) as f64) / 6000.0,
+
             "strength_incarnate" => character.stats.strength.min(100.0) / 100.0,
             "lightning_reflexes" => character.stats.dexterity.min(100.0) / 100.0,
             "iron_constitution" => character.stats.constitution.min(100.0) / 100.0,
@@ -335,6 +418,23 @@ impl AchievementTracker {
         }
     }
 
+    /// Get all titles this character has earned
+    pub fn get_unlocked_titles(&self, character: &Character) -> Vec<&'static Title> {
+        TITLES
+            .iter()
+            .filter(|t| self.is_title_unlocked(t, character))
+            .collect()
+    }
+
+    /// Check if a specific title's conditions are met
+    pub fn is_title_unlocked(&self, title: &Title, character: &Character) -> bool {
+        match title.unlock {
+            TitleUnlock::Achievement(id) => self.unlocked.contains(id),
+            TitleUnlock::Quests(count) => self.progress.quests_completed >= count,
+            TitleUnlock::Prestige(rank) => character.prestige_rank >= rank,
+        }
+    }
+
     /// Get achievements by tier
     pub fn get_achievements_by_tier(&self, tier: crate::achievements::AchievementTier) -> Vec<&'static Achievement> {
         ACHIEVEMENTS
@@ -354,3 +454,71 @@ impl AchievementTracker {
 
 // Re-export TaskTiming from progression module
 use crate::progression::TaskTiming;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_streak() {
+        let mut tracker = AchievementTracker::new();
+
+        tracker.update_streak(1, 0);
+        tracker.update_streak(0, 0); // same day
+        tracker.update_streak(1, 0);
+        assert_eq!(tracker.progress.current_streak, 2);
+
+        // One missed day breaks the streak without WIS
+        tracker.update_streak(2, 0);
+        assert_eq!(tracker.progress.current_streak, 1);
+        assert_eq!(tracker.progress.longest_streak, 2);
+
+        // ...but not with a grace day
+        tracker.update_streak(2, 1);
+        assert_eq!(tracker.progress.current_streak, 2);
+    }
+
+    #[test]
+    fn test_late_credit_keeps_streak() {
+        let character = Character::new("Test".to_string(), crate::character::Class::Warrior);
+        let mut tracker = AchievementTracker::new();
+        let day = |d| NaiveDate::from_ymd_opt(2024, 3, d).unwrap();
+
+        let missed = MissedDays { grace_days: 0, rest: &RestDays::default() };
+        tracker.record_quest_completion(&character, 5, TaskTiming::NoDueDate, None, day(10), missed);
+        tracker.record_quest_completion(&character, 5, TaskTiming::NoDueDate, None, day(11), missed);
+        tracker.record_quest_completion(&character, 5, TaskTiming::NoDueDate, None, day(2), missed);
+
+        assert_eq!(tracker.progress.current_streak, 2);
+        assert_eq!(tracker.progress.last_activity_date.as_deref(), Some("2024-03-11"));
+        assert_eq!(tracker.progress.quests_completed, 3);
+        assert!(tracker.progress.active_days.contains("2024-03-02"));
+    }
+
+    #[test]
+    fn test_rest_days_pause_streak_and_breaks() {
+        let character = Character::new("Test".to_string(), crate::character::Class::Warrior);
+        let mut tracker = AchievementTracker::new();
+        let day = |m, d| NaiveDate::from_ymd_opt(2024, m, d).unwrap();
+        let rest = RestDays {
+            weekly: vec![chrono::Weekday::Sat, chrono::Weekday::Sun],
+            periods: vec![crate::character::RestPeriod { start: "2024-03-11".to_string(), end: Some("2024-04-21".to_string()) }],
+        };
+        let missed = MissedDays { grace_days: 0, rest: &rest };
+
+        // Friday, then every weekday up to a six week vacation and the Monday after it
+        for (m, d) in [(3, 1), (3, 4), (3, 5), (3, 6), (3, 7), (3, 8), (4, 22)] {
+            tracker.record_quest_completion(&character, 5, TaskTiming::NoDueDate, None, day(m, d), missed);
+        }
+        assert_eq!(tracker.progress.current_streak, 7);
+        assert!(!tracker.progress.had_30_day_break);
+
+        // Without rest days that was a break
+        let mut tracker = AchievementTracker::new();
+        let missed = MissedDays { grace_days: 0, rest: &RestDays::default() };
+        tracker.record_quest_completion(&character, 5, TaskTiming::NoDueDate, None, day(3, 8), missed);
+        tracker.record_quest_completion(&character, 5, TaskTiming::NoDueDate, None, day(4, 22), missed);
+        assert!(tracker.progress.had_30_day_break);
+        assert_eq!(tracker.progress.current_streak, 1);
+    }
+}
@@ -35,16 +35,16 @@ mod tests {
     #[test]
     fn test_level_progression() {
         assert_eq!(LevelSystem::xp_for_level(1), 0);
-        assert_eq!(LevelSystem::xp_for_level(2), 121);
-        assert_eq!(LevelSystem::xp_for_level(3), 483);
-        assert_eq!(LevelSystem::xp_for_level(5), 2023);
+        assert_eq!(LevelSystem::xp_for_level(2), 428);
+        assert_eq!(LevelSystem::xp_for_level(3), 1004);
+        assert_eq!(LevelSystem::xp_for_level(5), 2936);
     }
 
     #[test]
     fn test_level_from_xp() {
         assert_eq!(LevelSystem::level_from_xp(0), 1);
-        assert_eq!(LevelSystem::level_from_xp(120), 1);
-        assert_eq!(LevelSystem::level_from_xp(121), 2);
-        assert_eq!(LevelSystem::level_from_xp(500), 3);
+        assert_eq!(LevelSystem::level_from_xp(427), 1);
+        assert_eq!(LevelSystem::level_from_xp(428), 2);
+        assert_eq!(LevelSystem::level_from_xp(1004), 3);
     }
 }
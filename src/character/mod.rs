@@ -2,15 +2,24 @@ pub mod stats;
 pub mod class;
 pub mod level;
 pub mod avatars;
+pub mod prestige;
+pub mod rest;
 
 pub use stats::Stats;
 pub use class::Class;
 pub use level::LevelSystem;
+pub use prestige::{PrestigeRecord, PrestigeSystem};
+pub use rest::{RestConfig, RestDays, RestPeriod};
 
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
+use crate::shop::savings::{self, SavingsGoal};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Character {
+    #[serde(default)]
+    pub schema_version: u32,
     pub name: String,
     pub class: Class,
     pub level: u32,
@@ -19,6 +28,14 @@ pub struct Character {
     pub gold: u32,
     pub tasks_completed: u32,
     pub active_title: Option<String>,
+    #[serde(default)]
+    pub prestige_rank: u32,
+    #[serde(default)]
+    pub prestige_history: Vec<PrestigeRecord>,
+    #[serde(default)]
+    pub savings_goals: Vec<SavingsGoal>,
+    #[serde(default)]
+    pub rest_periods: Vec<RestPeriod>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -31,6 +48,19 @@ pub enum StatType {
     CHA,
 }
 
+impl StatType {
+    pub fn all() -> &'static [StatType] {
+        &[
+            StatType::STR,
+            StatType::DEX,
+            StatType::CON,
+            StatType::INT,
+            StatType::WIS,
+            StatType::CHA,
+        ]
+    }
+}
+
 impl std::str::FromStr for StatType {
     type Err = anyhow::Error;
 
@@ -50,6 +80,7 @@ impl std::str::FromStr for StatType {
 impl Character {
     pub fn new(name: String, class: Class) -> Self {
         Self {
+            schema_version: <Self as crate::storage::Versioned>::SCHEMA_VERSION,
             name,
             class,
             level: 1,
@@ -58,6 +89,10 @@ impl Character {
             gold: 0,
             tasks_completed: 0,
             active_title: None,
+            prestige_rank: 0,
+            prestige_history: Vec::new(),
+            savings_goals: Vec::new(),
+            rest_periods: Vec::new(),
         }
     }
 
@@ -70,14 +105,93 @@ impl Character {
         self.gold += gold;
     }
 
+    /// Gold set aside for savings goals
+    pub fn reserved_gold(&self) -> u32 {
+        self.savings_goals.iter().map(|g| g.reserved).sum::<u32>().min(self.gold)
+    }
+
+    /// Gold that can be spent on a reward: everything not reserved for
+    /// other rewards' goals
+    pub fn spendable_gold_for(&self, reward_id: u32) -> u32 {
+        let reserved_elsewhere: u32 = self.savings_goals.iter()
+            .filter(|g| g.reward_id != reward_id)
+            .map(|g| g.reserved)
+            .sum();
+        self.gold.saturating_sub(reserved_elsewhere)
+    }
+
+    /// Set aside part of a payout for the savings goals, returning the amount reserved
+    pub fn reserve_for_goals(&mut self, payout: u32) -> u32 {
+        savings::allocate(&mut self.savings_goals, payout)
+    }
+
+    /// Release reserved gold back to the spendable pool. With no amount the
+    /// goal is removed entirely. Returns the gold released.
+    pub fn release_goal(&mut self, reward_id: u32, amount: Option<u32>) -> anyhow::Result<u32> {
+        let index = self.savings_goals.iter()
+            .position(|g| g.reward_id == reward_id)
+            .ok_or_else(|| anyhow::anyhow!("No savings goal for reward {}", reward_id))?;
+
+        match amount {
+            Some(amount) => {
+                let goal = &mut self.savings_goals[index];
+                let released = amount.min(goal.reserved);
+                goal.reserved -= released;
+                Ok(released)
+            }
+            None => Ok(self.savings_goals.remove(index).reserved),
+        }
+    }
+
+    /// The rest period covering `day`, if any
+    pub fn resting_on(&self, day: NaiveDate) -> Option<&RestPeriod> {
+        self.rest_periods.iter().find(|p| p.contains(day))
+    }
+
+    /// Start resting on `today`, until ended or through `until`
+    pub fn start_rest(&mut self, today: NaiveDate, until: Option<NaiveDate>) -> anyhow::Result<&RestPeriod> {
+        if let Some(current) = self.resting_on(today) {
+            anyhow::bail!("Already resting since {}", current.start);
+        }
+        if until.is_some_and(|until| until < today) {
+            anyhow::bail!("The rest can't end before it starts");
+        }
+
+        self.rest_periods.push(RestPeriod {
+            start: today.format("%Y-%m-%d").to_string(),
+            end: until.map(|d| d.format("%Y-%m-%d").to_string()),
+        });
+        Ok(self.rest_periods.last().expect("just pushed"))
+    }
+
+    /// End the current rest. Today is back to normal; a rest started today
+    /// is removed. Returns the days rested.
+    pub fn end_rest(&mut self, today: NaiveDate) -> anyhow::Result<i64> {
+        let index = self.rest_periods.iter()
+            .position(|p| p.contains(today))
+            .ok_or_else(|| anyhow::anyhow!("Not resting. Start with 'taskquest rest start'"))?;
+
+        let start = self.rest_periods[index].start_date().unwrap_or(today);
+        if start >= today {
+            self.rest_periods.remove(index);
+            return Ok(0);
+        }
+        let yesterday = today.pred_opt().unwrap_or(today);
+        self.rest_periods[index].end = Some(yesterday.format("%Y-%m-%d").to_string());
+        Ok((today - start).num_days())
+    }
+
     pub fn complete_task(&mut self, challenge: u8, stat1: Option<StatType>, stat2: Option<StatType>) {
         self.tasks_completed += 1;
 
         // Base formula: challenge / 20.0
         // Challenge 1: 20 tasks per point, Challenge 5: 4 tasks per point, Challenge 10: 2 tasks per point
-        // Diminishing returns are applied inside increase_stat()
-        let base_gain = challenge as f64 / 20.0;
+        self.train_stats(challenge as f64 / 20.0, stat1, stat2);
+    }
 
+    /// Train stat1 by `base_gain` and stat2 by half of it.
+    /// Diminishing returns are applied inside increase_stat()
+    pub fn train_stats(&mut self, base_gain: f64, stat1: Option<StatType>, stat2: Option<StatType>) {
         // stat1 gets full gain, stat2 gets half
         let stat1_gain = base_gain;
         let stat2_gain = base_gain / 2.0;
@@ -91,6 +205,47 @@ impl Character {
         }
     }
 
+    /// Reset level and XP in exchange for a permanent prestige rank.
+    /// Stats, gold and achievements are kept.
+    pub fn prestige(&mut self) -> anyhow::Result<&PrestigeRecord> {
+        if !PrestigeSystem::can_prestige(self.level) {
+            anyhow::bail!(
+                "Prestige requires level {} (currently level {})",
+                prestige::PRESTIGE_LEVEL,
+                self.level
+            );
+        }
+
+        self.prestige_rank += 1;
+        self.prestige_history.push(PrestigeRecord {
+            rank: self.prestige_rank,
+            date: chrono::Utc::now().to_rfc3339(),
+            level: self.level,
+            total_xp: self.total_xp,
+            tasks_completed: self.tasks_completed,
+        });
+
+        self.level = 1;
+        self.total_xp = 0;
+
+        Ok(self.prestige_history.last().expect("record was just pushed"))
+    }
+
+    /// Character name followed by the active title, if any
+    pub fn display_name(&self) -> String {
+        match &self.active_title {
+            Some(title) => format!("{} \"{}\"", self.name, title),
+            None => self.name.clone(),
+        }
+    }
+
+    /// XP earned across all prestige runs, including the current one
+    pub fn lifetime_xp(&self) -> u64 {
+        self.prestige_history.iter()
+            .map(|record| record.total_xp as u64)
+            .sum::<u64>() + self.total_xp as u64
+    }
+
     pub fn xp_to_next_level(&self) -> u32 {
         let next_level_xp = LevelSystem::xp_for_level(self.level + 1);
         next_level_xp.saturating_sub(self.total_xp)
@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+
+/// Level a character must reach before they can prestige
+pub const PRESTIGE_LEVEL: u32 = 50;
+
+/// XP and gold bonus granted per prestige rank (5% each)
+const BONUS_PER_RANK: f64 = 0.05;
+
+/// Snapshot of a character taken at the moment they prestiged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrestigeRecord {
+    pub rank: u32,
+    pub date: String, // ISO 8601 datetime
+    pub level: u32,
+    pub total_xp: u32,
+    pub tasks_completed: u32,
+}
+
+/// Prestige (New Game+) rules
+pub struct PrestigeSystem;
+
+impl PrestigeSystem {
+    /// Check whether a character at this level may prestige
+    pub fn can_prestige(level: u32) -> bool {
+        level >= PRESTIGE_LEVEL
+    }
+
+    /// XP multiplier for a prestige rank (1.0 at rank 0)
+    pub fn xp_multiplier(rank: u32) -> f64 {
+        1.0 + rank as f64 * BONUS_PER_RANK
+    }
+
+    /// Gold multiplier for a prestige rank (1.0 at rank 0)
+    pub fn gold_multiplier(rank: u32) -> f64 {
+        1.0 + rank as f64 * BONUS_PER_RANK
+    }
+
+    /// Bonus percentage shown to the user
+    pub fn bonus_percent(rank: u32) -> u32 {
+        (rank as f64 * BONUS_PER_RANK * 100.0).round() as u32
+    }
+
+    /// Star banner displayed above the avatar (empty at rank 0)
+    pub fn stars(rank: u32) -> String {
+        match rank {
+            0 => String::new(),
+            1..=5 => vec!["★"; rank as usize].join(" "),
+            _ => format!("★ x{}", rank),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_can_prestige() {
+        assert!(!PrestigeSystem::can_prestige(49));
+        assert!(PrestigeSystem::can_prestige(50));
+        assert!(PrestigeSystem::can_prestige(55));
+    }
+
+    #[test]
+    fn test_multipliers() {
+        assert_eq!(PrestigeSystem::xp_multiplier(0), 1.0);
+        assert!((PrestigeSystem::xp_multiplier(2) - 1.10).abs() < 1e-9);
+        assert!((PrestigeSystem::gold_multiplier(3) - 1.15).abs() < 1e-9);
+        assert_eq!(PrestigeSystem::bonus_percent(4), 20);
+    }
+
+    #[test]
+    fn test_character_prestige() {
+        use crate::character::{Character, Class};
+
+        let mut character = Character::new("Hero".to_string(), Class::Warrior);
+        assert!(character.prestige().is_err());
+
+        character.add_xp(400_000);
+        character.add_gold(250);
+        let level = character.level;
+        assert!(level >= PRESTIGE_LEVEL);
+
+        let record = character.prestige().unwrap();
+        assert_eq!(record.rank, 1);
+        assert_eq!(record.level, level);
+
+        assert_eq!(character.level, 1);
+        assert_eq!(character.total_xp, 0);
+        assert_eq!(character.gold, 250);
+        assert_eq!(character.prestige_rank, 1);
+        assert_eq!(character.lifetime_xp(), 400_000);
+    }
+
+    #[test]
+    fn test_stars() {
+        assert_eq!(PrestigeSystem::stars(0), "");
+        assert_eq!(PrestigeSystem::stars(3), "★ ★ ★");
+        assert_eq!(PrestigeSystem::stars(8), "★ x8");
+    }
+}
@@ -0,0 +1,120 @@
+//! Rest days: time off that time-based mechanics skip.
+//!
+//! Rest periods (`taskquest rest start|end`) and planned weekly rest days
+//! don't count as missed days: streaks and habits neither break nor decay
+//! over them, and they don't add up to a long break.
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Time off, `start` to `end` inclusive (ISO dates)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RestPeriod {
+    pub start: String,
+    /// None until the rest is ended
+    pub end: Option<String>,
+}
+
+impl RestPeriod {
+    pub fn start_date(&self) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(&self.start, "%Y-%m-%d").ok()
+    }
+
+    pub fn end_date(&self) -> Option<NaiveDate> {
+        self.end.as_deref().and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+    }
+
+    pub fn contains(&self, day: NaiveDate) -> bool {
+        self.start_date().is_some_and(|start| start <= day)
+            && self.end_date().is_none_or(|end| day <= end)
+    }
+}
+
+/// `rest` section of config.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RestConfig {
+    /// Days of the week that are always rest days, e.g. `["sat", "sun"]`
+    #[serde(serialize_with = "serialize_weekdays", deserialize_with = "deserialize_weekdays")]
+    pub weekly: Vec<Weekday>,
+}
+
+fn serialize_weekdays<S: Serializer>(days: &[Weekday], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(days.iter().map(|d| d.to_string().to_lowercase()))
+}
+
+fn deserialize_weekdays<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Weekday>, D::Error> {
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|day| day.parse::<Weekday>()
+            .map_err(|_| serde::de::Error::custom(format!("invalid weekday: {}", day))))
+        .collect()
+}
+
+/// Every rest day of a profile: its weekly rest days and rest periods
+#[derive(Debug, Clone, Default)]
+pub struct RestDays {
+    pub weekly: Vec<Weekday>,
+    pub periods: Vec<RestPeriod>,
+}
+
+impl RestDays {
+    pub fn new(config: &RestConfig, periods: &[RestPeriod]) -> Self {
+        Self {
+            weekly: config.weekly.clone(),
+            periods: periods.to_vec(),
+        }
+    }
+
+    /// Inside a rest period (weekly rest days aside)
+    pub fn in_period(&self, day: NaiveDate) -> bool {
+        self.periods.iter().any(|p| p.contains(day))
+    }
+
+    pub fn is_rest_day(&self, day: NaiveDate) -> bool {
+        self.weekly.contains(&day.weekday()) || self.in_period(day)
+    }
+
+    /// Days after `from`, up to and including `to`, that aren't rest days
+    pub fn active_days_between(&self, from: NaiveDate, to: NaiveDate) -> i64 {
+        Self::days_after(from, to).filter(|day| !self.is_rest_day(*day)).count() as i64
+    }
+
+    /// Days after `from`, up to and including `to`, inside rest periods
+    pub fn period_days_between(&self, from: NaiveDate, to: NaiveDate) -> i64 {
+        if self.periods.is_empty() {
+            return 0;
+        }
+        Self::days_after(from, to).filter(|day| self.in_period(*day)).count() as i64
+    }
+
+    fn days_after(from: NaiveDate, to: NaiveDate) -> impl Iterator<Item = NaiveDate> {
+        from.iter_days().skip(1).take_while(move |day| *day <= to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, d).unwrap()
+    }
+
+    #[test]
+    fn test_rest_days() {
+        // 2026-03-06 is a Friday
+        let config: RestConfig = serde_json::from_str(r#"{ "weekly": ["sat", "Sunday"] }"#).unwrap();
+        let mut rest = RestDays::new(&config, &[]);
+        assert!(rest.is_rest_day(day(7)) && !rest.is_rest_day(day(9)));
+        assert_eq!(rest.active_days_between(day(6), day(9)), 1);
+
+        rest.periods.push(RestPeriod { start: "2026-03-10".to_string(), end: Some("2026-03-12".to_string()) });
+        rest.periods.push(RestPeriod { start: "2026-03-20".to_string(), end: None });
+        assert_eq!(rest.active_days_between(day(9), day(16)), 2);
+        assert_eq!(rest.period_days_between(day(9), day(16)), 3);
+        assert!(rest.is_rest_day(day(25)));
+
+        assert!(serde_json::from_str::<RestConfig>(r#"{ "weekly": ["someday"] }"#).is_err());
+    }
+}
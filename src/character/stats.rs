@@ -1,8 +1,8 @@
 use serde::{Deserialize, Serialize};
 use super::StatType;
 
-const STAT_CAP: f64 = 99.0;
-const STAT_BASE: f64 = 10.0;
+pub const STAT_CAP: f64 = 99.0;
+pub const STAT_BASE: f64 = 10.0;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Stats {
@@ -60,14 +60,32 @@ impl Stats {
         let actual_gain = base_gain / difficulty;
 
         let new_value = (current + actual_gain).min(STAT_CAP);
+        self.set_stat_raw(stat_type, new_value);
+    }
 
+    /// Set raw stat value (internal f64)
+    pub fn set_stat_raw(&mut self, stat_type: StatType, value: f64) {
         match stat_type {
-            StatType::STR => self.strength = new_value,
-            StatType::DEX => self.dexterity = new_value,
-            StatType::CON => self.constitution = new_value,
-            StatType::INT => self.intelligence = new_value,
-            StatType::WIS => self.wisdom = new_value,
-            StatType::CHA => self.charisma = new_value,
+            StatType::STR => self.strength = value,
+            StatType::DEX => self.dexterity = value,
+            StatType::CON => self.constitution = value,
+            StatType::INT => self.intelligence = value,
+            StatType::WIS => self.wisdom = value,
+            StatType::CHA => self.charisma = value,
+        }
+    }
+
+    /// Whether a stat value can be reached in play (base to cap)
+    pub fn is_in_range(value: f64) -> bool {
+        (STAT_BASE..=STAT_CAP).contains(&value)
+    }
+
+    /// Nearest valid value for a stat; NaN resets to base
+    pub fn clamp_value(value: f64) -> f64 {
+        if value.is_nan() {
+            STAT_BASE
+        } else {
+            value.clamp(STAT_BASE, STAT_CAP)
         }
     }
 }
@@ -0,0 +1,79 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::character::RestConfig;
+use crate::events::EventsConfig;
+use crate::focus::FocusConfig;
+use crate::history::{CalibrationConfig, HabitConfig};
+use crate::progression::{FarmingConfig, StatEffects};
+use crate::storage::backups::BackupConfig;
+use crate::taskwarrior::ChallengeConfig;
+use crate::timewarrior::TimewConfig;
+
+/// User configuration stored in `config.json` in the data directory.
+/// Every section falls back to its defaults when missing.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Config {
+    #[serde(default)]
+    pub stat_effects: StatEffects,
+    #[serde(default)]
+    pub backup: BackupConfig,
+    #[serde(default)]
+    pub events: EventsConfig,
+    #[serde(default)]
+    pub challenge: ChallengeConfig,
+    #[serde(default)]
+    pub calibration: CalibrationConfig,
+    #[serde(default)]
+    pub timewarrior: TimewConfig,
+    #[serde(default)]
+    pub focus: FocusConfig,
+    #[serde(default)]
+    pub habits: HabitConfig,
+    #[serde(default)]
+    pub rest: RestConfig,
+    #[serde(default)]
+    pub farming: FarmingConfig,
+}
+
+impl Config {
+    pub fn load(data_dir: &Path) -> Result<Self> {
+        let path = data_dir.join("config.json");
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let file = std::fs::File::open(&path)
+            .context("Failed to open config file")?;
+        let config: Config = serde_json::from_reader(file)
+            .context("Failed to parse config JSON")?;
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_missing_config_uses_defaults() {
+        let dir = tempdir().unwrap();
+        let config = Config::load(dir.path()).unwrap();
+        assert_eq!(config.stat_effects.str_min_challenge, StatEffects::default().str_min_challenge);
+    }
+
+    #[test]
+    fn test_partial_config() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("config.json"),
+            r#"{ "stat_effects": { "int_xp_per_point": 0.01 } }"#,
+        ).unwrap();
+
+        let config = Config::load(dir.path()).unwrap();
+        assert_eq!(config.stat_effects.int_xp_per_point, 0.01);
+        assert_eq!(config.stat_effects.max_cha_discount, StatEffects::default().max_cha_discount);
+    }
+}
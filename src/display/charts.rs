@@ -0,0 +1,133 @@
+use chrono::{Datelike, Duration, NaiveDate};
+use colored::Colorize;
+use std::collections::BTreeMap;
+
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+const MONO_CELLS: [char; 5] = ['·', '░', '▒', '▓', '█'];
+const HEATMAP_WEEKS: i64 = 53;
+
+/// Terminal charts for activity history
+pub struct Charts;
+
+impl Charts {
+    /// Map a value onto 0 (none) through 4 (highest), relative to `max`
+    pub fn intensity(value: u32, max: u32) -> usize {
+        if value == 0 || max == 0 {
+            return 0;
+        }
+        let level = (value as f64 / max as f64 * 4.0).ceil() as usize;
+        level.clamp(1, 4)
+    }
+
+    /// Render values as a one-line sparkline
+    pub fn sparkline(values: &[u32]) -> String {
+        let max = values.iter().copied().max().unwrap_or(0);
+        let min = values.iter().copied().min().unwrap_or(0);
+        let range = (max - min) as f64;
+
+        values.iter()
+            .map(|&v| {
+                if range == 0.0 {
+                    if max == 0 { SPARK_CHARS[0] } else { SPARK_CHARS[3] }
+                } else {
+                    let idx = ((v - min) as f64 / range * (SPARK_CHARS.len() - 1) as f64).round() as usize;
+                    SPARK_CHARS[idx]
+                }
+            })
+            .collect()
+    }
+
+    /// GitHub-style calendar heatmap ending at `end`, one row per weekday
+    pub fn heatmap(values: &BTreeMap<NaiveDate, u32>, end: NaiveDate, mono: bool) -> Vec<String> {
+        // Start on the Monday 52 weeks before the week containing `end`
+        let end_monday = end - Duration::days(end.weekday().num_days_from_monday() as i64);
+        let start = end_monday - Duration::weeks(HEATMAP_WEEKS - 1);
+        let max = values.range(start..=end).map(|(_, v)| *v).max().unwrap_or(0);
+
+        // Month labels above the first week of each month
+        let mut header = String::from("    ");
+        let mut week = 0;
+        while week < HEATMAP_WEEKS {
+            let monday = start + Duration::weeks(week);
+            let label = monday.format("%b").to_string();
+            if monday.day() <= 7 && week + 2 < HEATMAP_WEEKS {
+                header.push_str(&label);
+                week += 3;
+            } else {
+                header.push(' ');
+                week += 1;
+            }
+        }
+
+        let mut lines = vec![header];
+        for (weekday, name) in ["Mon", "", "Wed", "", "Fri", "", "Sun"].iter().enumerate() {
+            let mut line = format!("{:<4}", name);
+            for week in 0..HEATMAP_WEEKS {
+                let day = start + Duration::weeks(week) + Duration::days(weekday as i64);
+                if day > end {
+                    line.push(' ');
+                    continue;
+                }
+                let level = Self::intensity(values.get(&day).copied().unwrap_or(0), max);
+                line.push_str(&Self::cell(level, mono));
+            }
+            lines.push(line);
+        }
+
+        lines
+    }
+
+    /// Legend from least to most active
+    pub fn legend(mono: bool) -> String {
+        let cells: String = (0..=4).map(|level| Self::cell(level, mono)).collect();
+        format!("Less {} More", cells)
+    }
+
+    fn cell(level: usize, mono: bool) -> String {
+        if mono {
+            return MONO_CELLS[level].to_string();
+        }
+
+        match level {
+            0 => "■".truecolor(45, 51, 59).to_string(),
+            1 => "■".truecolor(14, 68, 41).to_string(),
+            2 => "■".truecolor(0, 109, 50).to_string(),
+            3 => "■".truecolor(38, 166, 65).to_string(),
+            _ => "■".truecolor(57, 211, 83).to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intensity() {
+        assert_eq!(Charts::intensity(0, 100), 0);
+        assert_eq!(Charts::intensity(1, 100), 1);
+        assert_eq!(Charts::intensity(50, 100), 2);
+        assert_eq!(Charts::intensity(100, 100), 4);
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(Charts::sparkline(&[0, 7, 14]), "▁▅█");
+        assert_eq!(Charts::sparkline(&[0, 0]), "▁▁");
+        assert_eq!(Charts::sparkline(&[]), "");
+    }
+
+    #[test]
+    fn test_heatmap_shape() {
+        let end = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let mut values = BTreeMap::new();
+        values.insert(end, 5);
+
+        let lines = Charts::heatmap(&values, end, true);
+        assert_eq!(lines.len(), 8); // header + 7 weekdays
+
+        // 2026-10-18 is a Sunday: last column of the Sunday row is the most intense
+        assert!(lines[7].ends_with('█'));
+        assert_eq!(lines[1].chars().count(), 4 + HEATMAP_WEEKS as usize);
+    }
+}
@@ -3,17 +3,23 @@ use clap::{Parser, Subcommand};
 use std::io::{self, Write};
 use std::str::FromStr;
 
-use crate::character::{Character, Class};
-use crate::storage::{get_data_dir, safe_write, load_with_backup};
-use crate::taskwarrior::UDAManager;
+use crate::character::{Character, Class, PrestigeSystem};
+use crate::storage::get_data_dir;
+use crate::taskwarrior::{TaskData, UDAManager};
 use crate::display::Formatter;
-use crate::shop::RewardStore;
-use crate::achievements::AchievementTracker;
+use crate::shop::PricingMode;
+use crate::config::Config;
+use crate::events::Events;
+use crate::game::Profile;
 
 #[derive(Parser)]
 #[command(name = "taskquest")]
 #[command(about = "Gamified RPG system for Taskwarrior", long_about = None)]
 pub struct CLI {
+    /// Profile to use (e.g. work/personal)
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -37,7 +43,10 @@ pub enum Commands {
     /// Set character class
     Class { class: String },
     /// List available shop rewards
-    Shop,
+    Shop {
+        #[command(subcommand)]
+        action: Option<ShopAction>,
+    },
     /// Purchase a reward from the shop
     Buy {
         /// Reward ID or name to purchase
@@ -59,17 +68,372 @@ pub enum Commands {
         /// Cooldown in hours (optional)
         #[arg(long, default_value = "0")]
         cooldown: u32,
+        /// Always charge the listed cost, even with dynamic pricing
+        #[arg(long)]
+        fixed_price: bool,
     },
     /// Remove a reward from the shop
     RemoveReward {
         /// Reward ID or name to remove
         reward: String,
+        /// Delete a default reward (1-10) instead of hiding it
+        #[arg(long)]
+        force: bool,
+    },
+    /// Edit, hide or list rewards
+    Reward {
+        #[command(subcommand)]
+        action: RewardAction,
+    },
+    /// Save up for a reward by setting gold aside as you earn it
+    Goal {
+        #[command(subcommand)]
+        action: GoalAction,
+    },
+    /// Take time off without breaking streaks or habits
+    Rest {
+        #[command(subcommand)]
+        action: RestAction,
+    },
+    /// Credit completed tasks that never went through the hook
+    Reconcile {
+        /// Only show what would be credited
+        #[arg(long)]
+        dry_run: bool,
+        /// Only consider tasks completed on or after this date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+        /// Read a saved 'task export' file instead of running Taskwarrior
+        #[arg(long)]
+        file: Option<std::path::PathBuf>,
+        /// Itemise each quest's XP, gold and loot roll
+        #[arg(long, short)]
+        verbose: bool,
+    },
+    /// Show how a completed quest's XP, gold and loot were worked out
+    Explain {
+        /// Task UUID (or a unique prefix of it)
+        uuid: String,
+    },
+    /// Show the challenge a new task would get, and why
+    SuggestChallenge {
+        /// Task description
+        description: String,
+        /// Task project
+        #[arg(long)]
+        project: Option<String>,
+        /// Task tag (repeatable)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// Task priority (H/M/L)
+        #[arg(long)]
+        priority: Option<String>,
+        /// Estimated duration (e.g. 45min, 2h, PT1H30M)
+        #[arg(long)]
+        estimate: Option<String>,
+    },
+    /// Compare how long quests took with their challenge, per project
+    Calibration,
+    /// Run a focus (pomodoro) session on a task for XP and stat training
+    Focus {
+        /// Task ID or UUID
+        task: String,
+        /// Session length in minutes (default from config, 25)
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..=240))]
+        minutes: Option<u32>,
+    },
+    /// Show focus time tracked in Timewarrior and credit deep work days
+    DeepWork {
+        /// Number of days to show
+        #[arg(long, default_value = "7")]
+        days: i64,
+        /// Read a saved 'timew export' file instead of the configured source
+        #[arg(long)]
+        file: Option<std::path::PathBuf>,
+    },
+    /// List habits (recurring tasks) with their strength and streaks
+    Habits,
+    /// Summarise XP, gold and quests over a period
+    Report {
+        /// Period to cover (week/month/year)
+        #[arg(long, default_value = "week")]
+        period: String,
+        /// How to group completions (project/day/stat/challenge)
+        #[arg(long, default_value = "day")]
+        by: String,
+        /// Output JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show an activity heatmap and sparklines
+    Activity {
+        /// What to chart (xp/tasks)
+        #[arg(long, default_value = "xp")]
+        metric: String,
+        /// Number of days in the XP sparkline
+        #[arg(long, default_value = "30")]
+        days: i64,
+        /// Monochrome output (also used when colors are disabled)
+        #[arg(long)]
+        mono: bool,
+    },
+    /// Manage character titles
+    Title {
+        #[command(subcommand)]
+        action: TitleAction,
+    },
+    /// Prestige: reset level and XP for a permanent bonus (requires level 50)
+    Prestige {
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Snapshot and restore the data files
+    Backup {
+        #[command(subcommand)]
+        action: BackupAction,
+    },
+    /// Check data files, hooks, UDAs and git sync for problems
+    Doctor {
+        /// Repair the problems that can be fixed automatically
+        #[arg(long)]
+        fix: bool,
+    },
+    /// Manage profiles (separate characters, e.g. work/personal)
+    Profile {
+        #[command(subcommand)]
+        action: ProfileAction,
     },
     /// Sync commands for git-based synchronization
     Sync {
         #[command(subcommand)]
         action: SyncAction,
     },
+    /// Run one event handler with the event on stdin (used internally)
+    #[command(hide = true)]
+    EventHandler {
+        #[arg(long, default_value = "5")]
+        timeout: u64,
+        #[arg(last = true, required = true)]
+        command: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ShopAction {
+    /// Show or change dynamic pricing
+    Pricing {
+        /// fixed, level or income
+        #[arg(long)]
+        mode: Option<String>,
+        /// Level mode: price increase per level above 1 (0.02 = +2%)
+        #[arg(long)]
+        per_level: Option<f64>,
+        /// Income mode: gold per window at which prices equal the listed cost
+        #[arg(long)]
+        reference_income: Option<u32>,
+        /// Income mode: days of completions that count as recent income
+        #[arg(long)]
+        window_days: Option<u32>,
+        /// Lowest price multiplier
+        #[arg(long)]
+        floor: Option<f64>,
+        /// Highest price multiplier
+        #[arg(long)]
+        ceiling: Option<f64>,
+        /// Reward IDs that always cost their listed price
+        #[arg(long, value_delimiter = ',')]
+        fixed: Vec<u32>,
+        /// Reward IDs that follow dynamic pricing again
+        #[arg(long, value_delimiter = ',')]
+        dynamic: Vec<u32>,
+    },
+    /// Show past purchases, newest first
+    History {
+        /// Number of purchases to show
+        #[arg(default_value = "20")]
+        limit: usize,
+    },
+    /// Manage spending budgets
+    Budget {
+        #[command(subcommand)]
+        action: BudgetAction,
+    },
+    /// Write the reward catalog to a file (or stdout) to share it
+    Export {
+        /// Output file (.json or .toml); prints to stdout if omitted
+        output: Option<std::path::PathBuf>,
+        /// Format (json/toml), defaults to the file extension
+        #[arg(long)]
+        format: Option<String>,
+    },
+    /// Merge a reward catalog into the shop, matching rewards by name
+    Import {
+        /// Catalog file (.json or .toml)
+        file: std::path::PathBuf,
+        /// Format (json/toml), defaults to the file extension
+        #[arg(long)]
+        format: Option<String>,
+        /// Hide rewards that are not in the catalog
+        #[arg(long)]
+        replace: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum RewardAction {
+    /// List every reward, including hidden ones
+    List,
+    /// Change a reward's details
+    Edit {
+        /// Reward ID or name
+        reward: String,
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long)]
+        cost: Option<u32>,
+        #[arg(long)]
+        description: Option<String>,
+        /// Tier (normal/heroic/epic/legendary)
+        #[arg(long)]
+        tier: Option<String>,
+        /// Cooldown in hours
+        #[arg(long)]
+        cooldown: Option<u32>,
+        /// Opt out of (true) or back into (false) dynamic pricing
+        #[arg(long)]
+        fixed_price: Option<bool>,
+    },
+    /// Hide a reward from the shop without deleting it
+    Hide {
+        /// Reward ID or name
+        reward: String,
+    },
+    /// Offer a hidden reward again
+    Unhide {
+        /// Reward ID or name
+        reward: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum RestAction {
+    /// Start resting today
+    Start {
+        /// Last day of the rest (YYYY-MM-DD); open-ended unless given
+        #[arg(long)]
+        until: Option<String>,
+    },
+    /// End the current rest; today counts as a normal day
+    End,
+    /// Show rest days and rest periods
+    Status,
+}
+
+#[derive(Subcommand)]
+pub enum GoalAction {
+    /// List savings goals and their progress
+    List,
+    /// Start saving for a reward (or change how much is set aside)
+    Set {
+        /// Reward ID or name
+        reward: String,
+        /// Percentage of each quest's gold to set aside
+        #[arg(long, conflicts_with = "amount", required_unless_present = "amount",
+              value_parser = clap::value_parser!(u8).range(1..=100))]
+        percent: Option<u8>,
+        /// Fixed gold to set aside per quest
+        #[arg(long)]
+        amount: Option<u32>,
+    },
+    /// Make reserved gold spendable again (all of it unless --amount is given)
+    Release {
+        /// Reward ID or name
+        reward: String,
+        /// Gold to release; the goal keeps saving
+        #[arg(long)]
+        amount: Option<u32>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum BudgetAction {
+    /// List budgets and what is left of each
+    List,
+    /// Add a budget (for one reward, one tier, or all rewards)
+    Add {
+        /// Reward ID or name this budget applies to
+        #[arg(long, conflicts_with = "tier")]
+        reward: Option<String>,
+        /// Tier this budget applies to (normal/heroic/epic/legendary)
+        #[arg(long)]
+        tier: Option<String>,
+        /// Rolling window (day/week/month)
+        #[arg(long, default_value = "week")]
+        period: String,
+        /// Maximum number of purchases in the period
+        #[arg(long, conflicts_with = "max_gold", required_unless_present = "max_gold")]
+        max_count: Option<u32>,
+        /// Maximum gold spent in the period
+        #[arg(long)]
+        max_gold: Option<u32>,
+    },
+    /// Remove a budget by its number in 'budget list'
+    Remove { number: usize },
+}
+
+#[derive(Subcommand)]
+pub enum TitleAction {
+    /// List all titles and which ones are unlocked
+    List,
+    /// Set the active title
+    Set {
+        /// Title ID or name
+        title: String,
+    },
+    /// Remove the active title
+    Clear,
+}
+
+#[derive(Subcommand)]
+pub enum ProfileAction {
+    /// List profiles
+    List,
+    /// Create a new profile
+    Create {
+        name: String,
+        /// Route hook completions for projects starting with this prefix here
+        #[arg(long)]
+        project_prefix: Option<String>,
+        /// Route hook completions from this Taskwarrior data location here
+        #[arg(long)]
+        task_data: Option<String>,
+    },
+    /// Delete a profile and all of its data
+    Delete {
+        name: String,
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Make a profile the default
+    Switch { name: String },
+}
+
+#[derive(Subcommand)]
+pub enum BackupAction {
+    /// List snapshots, newest first
+    List,
+    /// Take a snapshot now
+    Create,
+    /// Replace the character, achievements, shop and history with a snapshot
+    Restore {
+        /// Snapshot ID (see 'taskquest backup list')
+        id: String,
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -96,22 +460,55 @@ pub enum SyncAction {
 
 impl CLI {
     pub fn run(self) -> Result<()> {
+        if let Some(ref profile) = self.profile {
+            crate::storage::profiles::select(profile)?;
+        }
+
         match self.command {
             Commands::Init { skip_wizard } => Self::init(skip_wizard),
             Commands::Status => Self::status(),
             Commands::Stats => Self::stats(),
             Commands::Name { name } => Self::set_name(name),
             Commands::Class { class } => Self::set_class(class),
-            Commands::Shop => Self::list_shop(),
+            Commands::Shop { action: None } => Self::list_shop(),
+            Commands::Shop { action: Some(action) } => Self::handle_shop(action),
             Commands::Buy { reward } => Self::buy_reward(reward),
             Commands::Achievements => Self::list_achievements(),
-            Commands::AddReward { name, cost, description, tier, cooldown } =>
-                Self::add_reward(name, cost, description, tier, cooldown),
-            Commands::RemoveReward { reward } => Self::remove_reward(reward),
+            Commands::AddReward { name, cost, description, tier, cooldown, fixed_price } =>
+                Self::add_reward(name, cost, description, tier, cooldown, fixed_price),
+            Commands::RemoveReward { reward, force } => Self::remove_reward(reward, force),
+            Commands::Reward { action } => Self::handle_reward(action),
+            Commands::Goal { action } => Self::handle_goal(action),
+            Commands::Rest { action } => Self::handle_rest(action),
+            Commands::Reconcile { dry_run, since, file, verbose } => Self::reconcile(dry_run, since, file, verbose),
+            Commands::Explain { uuid } => Self::explain(uuid),
+            Commands::SuggestChallenge { description, project, tags, priority, estimate } =>
+                Self::suggest_challenge(description, project, tags, priority, estimate),
+            Commands::Calibration => Self::calibration(),
+            Commands::Focus { task, minutes } => Self::focus(task, minutes),
+            Commands::DeepWork { days, file } => Self::deep_work(days, file),
+            Commands::Habits => Self::habits(),
+            Commands::Report { period, by, json } => Self::report(period, by, json),
+            Commands::Activity { metric, days, mono } => Self::activity(metric, days, mono),
+            Commands::Title { action } => Self::handle_title(action),
+            Commands::Prestige { yes } => Self::prestige(yes),
+            Commands::Backup { action } => Self::handle_backup(action),
+            Commands::Doctor { fix } => Self::doctor(fix),
+            Commands::Profile { action } => Self::handle_profile(action),
             Commands::Sync { action } => Self::handle_sync(action),
+            Commands::EventHandler { timeout, command } => Self::run_event_handler(timeout, command),
         }
     }
 
+    fn run_event_handler(timeout: u64, command: String) -> Result<()> {
+        use std::io::Read;
+
+        let mut payload = String::new();
+        io::stdin().read_to_string(&mut payload)?;
+        Events::run_handler(&command, &payload, std::time::Duration::from_secs(timeout))?;
+        Ok(())
+    }
+
     fn init(skip_wizard: bool) -> Result<()> {
         let data_dir = get_data_dir()?;
         let character_path = data_dir.join("character.json");
@@ -161,10 +558,9 @@ impl CLI {
         let class = Class::all()[class_idx];
 
         // Create character
-        let character = Character::new(name, class);
-
-        // Save character
-        safe_write(&character_path, &character)?;
+        let profile = Profile::create(&data_dir, Character::new(name, class))?;
+        profile.save()?;
+        let character = &profile.character;
 
         println!("\n✅ Character created successfully!");
         println!("\nYour hero: {}, the {}, Level {}",
@@ -178,36 +574,26 @@ impl CLI {
     }
 
     fn status() -> Result<()> {
-        let data_dir = get_data_dir()?;
-        let character_path = data_dir.join("character.json");
-
-        let character: Character = load_with_backup(&character_path)
-            .context("Character not found. Run 'taskquest init' first")?;
+        let profile = Profile::open_active()?;
 
-        Formatter::print_status(&character);
+        Formatter::print_status(&profile.character);
 
         Ok(())
     }
 
     fn stats() -> Result<()> {
-        let data_dir = get_data_dir()?;
-        let character_path = data_dir.join("character.json");
+        let profile = Profile::open_active()?;
+        let character = &profile.character;
 
-        let character: Character = load_with_backup(&character_path)
-            .context("Character not found. Run 'taskquest init' first")?;
-
-        Formatter::print_stats(&character);
+        Formatter::print_stats(character, &profile.config.stat_effects.bonuses(&character.stats));
 
         Ok(())
     }
 
     fn set_name(name: String) -> Result<()> {
-        let data_dir = get_data_dir()?;
-        let character_path = data_dir.join("character.json");
-
-        let mut character: Character = load_with_backup(&character_path)?;
-        character.name = name.clone();
-        safe_write(&character_path, &character)?;
+        let mut profile = Profile::open_active()?;
+        profile.character.name = name.clone();
+        profile.save()?;
 
         println!("✅ Character name set to: {}", name);
 
@@ -215,13 +601,10 @@ impl CLI {
     }
 
     fn set_class(class_str: String) -> Result<()> {
-        let data_dir = get_data_dir()?;
-        let character_path = data_dir.join("character.json");
-
         let class = Class::from_str(&class_str)?;
-        let mut character: Character = load_with_backup(&character_path)?;
-        character.class = class;
-        safe_write(&character_path, &character)?;
+        let mut profile = Profile::open_active()?;
+        profile.character.class = class;
+        profile.save()?;
 
         println!("✅ Character class set to: {}", class);
 
@@ -232,18 +615,18 @@ impl CLI {
         use colored::Colorize;
         use crate::progression::RewardTier;
 
-        let data_dir = get_data_dir()?;
-        let character_path = data_dir.join("character.json");
-        let shop_path = data_dir.join("shop.json");
-
-        let character: Character = load_with_backup(&character_path)?;
-        let shop = RewardStore::load(&shop_path)?;
+        let profile = Profile::open_active()?;
+        let (character, shop) = (&profile.character, &profile.shop);
+        let ctx = profile.price_context();
 
         println!();
         println!("{}", "╔════════════════════════════════════════════════════╗".cyan());
         println!("{}", "║              🏪  REWARD SHOP  🏪                   ║".cyan().bold());
         println!("{}", "╠════════════════════════════════════════════════════╣".cyan());
         println!("║ Your Gold: {} 💰                                    ║", character.gold.to_string().yellow().bold());
+        if character.reserved_gold() > 0 {
+            println!("║ Reserved for goals: {}                              ║", character.reserved_gold());
+        }
         println!("{}", "╚════════════════════════════════════════════════════╝".cyan());
         println!();
 
@@ -262,18 +645,29 @@ impl CLI {
                 RewardTier::Legendary => "yellow",
             };
 
-            let affordable = if character.gold >= reward.cost {
+            let cost = shop.cost_of(reward, &ctx);
+            let affordable = if character.spendable_gold_for(reward.id) >= cost {
                 "✓".green()
             } else {
                 "✗".red()
             };
 
-            println!("{}  [ID: {}] {} - {} Gold",
-                affordable,
-                reward.id.to_string().cyan(),
-                reward.name.color(tier_color).bold(),
-                reward.cost.to_string().yellow()
-            );
+            if cost != reward.cost {
+                println!("{}  [ID: {}] {} - {} Gold (base {})",
+                    affordable,
+                    reward.id.to_string().cyan(),
+                    reward.name.color(tier_color).bold(),
+                    cost.to_string().yellow(),
+                    reward.cost
+                );
+            } else {
+                println!("{}  [ID: {}] {} - {} Gold",
+                    affordable,
+                    reward.id.to_string().cyan(),
+                    reward.name.color(tier_color).bold(),
+                    reward.cost.to_string().yellow()
+                );
+            }
             println!("     {}", reward.description);
 
             if reward.cooldown_hours > 0 {
@@ -282,69 +676,212 @@ impl CLI {
             println!();
         }
 
+        let multiplier = shop.pricing.multiplier(&ctx);
+        if multiplier != 1.0 {
+            println!("Prices follow your {} (x{:.2})", shop.pricing.mode, multiplier);
+        }
+        if ctx.discount > 0.0 {
+            println!("CHA discount: {:.1}% off", ctx.discount * 100.0);
+        }
         println!("Use 'taskquest buy <id or name>' to purchase a reward");
         println!();
 
         Ok(())
     }
 
-    fn buy_reward(reward_identifier: String) -> Result<()> {
-        use colored::Colorize;
+    fn handle_shop(action: ShopAction) -> Result<()> {
+        let mut profile = Profile::open_active()?;
 
-        let data_dir = get_data_dir()?;
-        let character_path = data_dir.join("character.json");
-        let shop_path = data_dir.join("shop.json");
-        let achievements_path = data_dir.join("achievements.json");
+        match action {
+            ShopAction::Pricing { mode, per_level, reference_income, window_days, floor, ceiling, fixed, dynamic } => {
+                let changed = mode.is_some() || per_level.is_some() || reference_income.is_some()
+                    || window_days.is_some() || floor.is_some() || ceiling.is_some()
+                    || !fixed.is_empty() || !dynamic.is_empty();
+
+                let pricing = &mut profile.shop.pricing;
+                if let Some(mode) = mode {
+                    pricing.mode = PricingMode::from_str(&mode)?;
+                }
+                if let Some(per_level) = per_level {
+                    pricing.per_level = per_level;
+                }
+                if let Some(reference_income) = reference_income {
+                    pricing.reference_income = reference_income;
+                }
+                if let Some(window_days) = window_days {
+                    pricing.income_window_days = window_days;
+                }
+                if let Some(floor) = floor {
+                    pricing.floor = floor;
+                }
+                if let Some(ceiling) = ceiling {
+                    pricing.ceiling = ceiling;
+                }
+                if pricing.floor < 0.0 || pricing.ceiling < pricing.floor {
+                    anyhow::bail!("Price floor must be at least 0 and no higher than the ceiling");
+                }
+                for (ids, fixed_price) in [(&fixed, true), (&dynamic, false)] {
+                    for id in ids {
+                        profile.shop.rewards.iter_mut()
+                            .find(|r| r.id == *id)
+                            .with_context(|| format!("Reward {} not found", id))?
+                            .fixed_price = fixed_price;
+                    }
+                }
 
-        let mut character: Character = load_with_backup(&character_path)?;
-        let mut shop = RewardStore::load(&shop_path)?;
-        let mut tracker = AchievementTracker::load(&achievements_path)
-            .unwrap_or_else(|_| AchievementTracker::new());
+                if changed {
+                    profile.save()?;
+                    println!("✅ Pricing updated");
+                }
 
-        // Try to parse as ID first, otherwise search by name
-        let reward_id = if let Ok(id) = reward_identifier.parse::<u32>() {
-            id
-        } else {
-            shop.get_reward_by_name(&reward_identifier)
-                .map(|r| r.id)
-                .context(format!("Reward '{}' not found", reward_identifier))?
-        };
+                let pricing = &profile.shop.pricing;
+                println!();
+                println!("Mode:       {}", pricing.mode);
+                match pricing.mode {
+                    PricingMode::Fixed => {}
+                    PricingMode::Level => println!("Per level:  +{:.1}%", pricing.per_level * 100.0),
+                    PricingMode::Income => println!("Reference:  {} gold per {} days",
+                        pricing.reference_income, pricing.income_window_days),
+                }
+                println!("Range:      x{:.2} to x{:.2}", pricing.floor, pricing.ceiling);
+                let fixed_names: Vec<_> = profile.shop.rewards.iter()
+                    .filter(|r| r.fixed_price)
+                    .map(|r| format!("{} ({})", r.name, r.id))
+                    .collect();
+                if !fixed_names.is_empty() {
+                    println!("Fixed:      {}", fixed_names.join(", "));
+                }
+                println!();
+            }
+            ShopAction::History { limit } => {
+                Formatter::print_purchase_history(&profile.shop.purchases, limit);
+            }
+            ShopAction::Budget { action } => Self::handle_budget(action, &mut profile)?,
+            ShopAction::Export { output, format } => {
+                use crate::shop::catalog::{Catalog, CatalogFormat};
+
+                let format = match (&format, &output) {
+                    (Some(format), _) => CatalogFormat::from_str(format)?,
+                    (None, Some(path)) => CatalogFormat::from_path(path),
+                    (None, None) => CatalogFormat::Json,
+                };
+                let text = Catalog::from_store(&profile.shop).render(format)?;
+
+                match output {
+                    Some(path) => {
+                        std::fs::write(&path, text)
+                            .with_context(|| format!("Failed to write {}", path.display()))?;
+                        println!("✅ Exported {} rewards to {}",
+                            Catalog::from_store(&profile.shop).rewards.len(), path.display());
+                    }
+                    None => println!("{}", text),
+                }
+            }
+            ShopAction::Import { file, format, replace } => {
+                use crate::shop::catalog::{Catalog, CatalogFormat};
+
+                let format = match format {
+                    Some(format) => CatalogFormat::from_str(&format)?,
+                    None => CatalogFormat::from_path(&file),
+                };
+                let text = std::fs::read_to_string(&file)
+                    .with_context(|| format!("Failed to read {}", file.display()))?;
+                let catalog = Catalog::parse(&text, format)?;
+
+                let summary = profile.shop.import_catalog(&catalog, replace);
+                profile.save()?;
+
+                println!("✅ Imported {}: {} added, {} updated, {} hidden",
+                    file.display(), summary.added, summary.updated, summary.hidden);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_budget(action: BudgetAction, profile: &mut Profile) -> Result<()> {
+        use crate::progression::RewardTier;
+        use crate::shop::{Budget, BudgetLimit, BudgetPeriod, BudgetScope};
+
+        match action {
+            BudgetAction::List => {
+                if profile.shop.budgets.is_empty() {
+                    println!("No budgets set. Add one with 'taskquest shop budget add'");
+                    return Ok(());
+                }
 
-        // Get reward info for display
-        let reward_name = shop.get_reward(reward_id)
-            .context("Reward not found")?
-            .name.clone();
+                let now = chrono::Utc::now();
+                println!();
+                for (i, budget) in profile.shop.budgets.iter().enumerate() {
+                    let remaining = budget.remaining(&profile.shop.purchases, now);
+                    let left = match budget.limit {
+                        BudgetLimit::Count(_) => format!("{} left", remaining),
+                        BudgetLimit::Gold(_) => format!("{} gold left", remaining),
+                    };
+                    println!("{}. {} ({})", i + 1, budget.describe(&profile.shop.rewards), left);
+                }
+                println!();
+            }
+            BudgetAction::Add { reward, tier, period, max_count, max_gold } => {
+                let scope = if let Some(reward) = reward {
+                    BudgetScope::Reward(profile.shop.find_reward_id(&reward)?)
+                } else if let Some(tier) = tier {
+                    BudgetScope::Tier(RewardTier::from_str(&tier)?)
+                } else {
+                    BudgetScope::All
+                };
+                let limit = match (max_count, max_gold) {
+                    (Some(count), _) => BudgetLimit::Count(count),
+                    (None, Some(gold)) => BudgetLimit::Gold(gold),
+                    (None, None) => anyhow::bail!("Set --max-count or --max-gold"),
+                };
+
+                let budget = Budget { scope, period: BudgetPeriod::from_str(&period)?, limit };
+                println!("✅ Budget added: {}", budget.describe(&profile.shop.rewards));
+                profile.shop.budgets.push(budget);
+                profile.save()?;
+            }
+            BudgetAction::Remove { number } => {
+                if number == 0 || number > profile.shop.budgets.len() {
+                    anyhow::bail!("No budget number {}. See 'taskquest shop budget list'", number);
+                }
+                let budget = profile.shop.budgets.remove(number - 1);
+                profile.save()?;
+                println!("🗑️  Budget removed: {}", budget.describe(&profile.shop.rewards));
+            }
+        }
 
-        // Attempt purchase
-        let cost = shop.purchase_reward(reward_id, character.gold)?;
+        Ok(())
+    }
 
-        // Deduct gold from character
-        character.gold -= cost;
+    fn buy_reward(reward_identifier: String) -> Result<()> {
+        use colored::Colorize;
 
-        // Track purchase in achievements
-        let new_achievements = tracker.record_reward_purchase(&character);
+        let mut profile = Profile::open_active()?;
+        let purchase = profile.purchase(&reward_identifier)?;
+        profile.save()?;
 
-        // Save all changes
-        safe_write(&character_path, &character)?;
-        safe_write(&shop_path, &shop)?;
-        safe_write(&achievements_path, &tracker)?;
+        Events::emit(&profile.config.events, &profile.character.name, &purchase.events());
 
         // Print success message
         println!();
         println!("{}", "╔════════════════════════════════════════╗".green());
         println!("{}", "║      ✨ PURCHASE SUCCESSFUL! ✨        ║".green().bold());
         println!("{}", "╠════════════════════════════════════════╣".green());
-        println!("║ Reward: {}                             ║", reward_name.bold());
-        println!("║ Cost: {} Gold                          ║", cost.to_string().yellow());
-        println!("║ Remaining Gold: {}                     ║", character.gold.to_string().yellow().bold());
+        println!("║ Reward: {}                             ║", purchase.reward_name.bold());
+        println!("║ Cost: {} Gold                          ║", purchase.cost.to_string().yellow());
+        println!("║ Remaining Gold: {}                     ║", purchase.gold_after.to_string().yellow().bold());
         println!("{}", "╚════════════════════════════════════════╝".green());
         println!();
+        if purchase.goal_completed {
+            println!("🎯 Savings goal complete!");
+        }
         println!("Enjoy your reward! 🎉");
         println!();
 
         // Display any new achievements
-        if !new_achievements.is_empty() {
-            for achievement in new_achievements {
+        if !purchase.achievements.is_empty() {
+            for achievement in &purchase.achievements {
                 let color = achievement.tier.color_code();
                 println!();
                 println!("{}", "╔════════════════════════════════════════╗".yellow());
@@ -367,13 +904,9 @@ impl CLI {
     fn list_achievements() -> Result<()> {
         use colored::Colorize;
 
-        let data_dir = get_data_dir()?;
-        let character_path = data_dir.join("character.json");
-        let achievements_path = data_dir.join("achievements.json");
-
-        let character: Character = load_with_backup(&character_path)?;
-        let tracker = AchievementTracker::load(&achievements_path)
-            .unwrap_or_else(|_| AchievementTracker::new());
+        let profile = Profile::open_active()?;
+        let character = &profile.character;
+        let tracker = &profile.achievements;
 
         println!();
         println!("{}", "╔════════════════════════════════════════════════════╗".yellow());
@@ -406,7 +939,7 @@ impl CLI {
                 let color = tier.color_code();
                 println!("{}", format!("=== {} ===", tier.name()).color(color).bold());
                 for achievement in tier_achievements {
-                    let progress = tracker.get_achievement_progress(achievement.id, &character);
+                    let progress = tracker.get_achievement_progress(achievement.id, character);
                     println!("{} {} - {}",
                         achievement.icon,
                         achievement.title.color(color).bold(),
@@ -431,29 +964,25 @@ impl CLI {
         Ok(())
     }
 
-    fn add_reward(name: String, cost: u32, description: String, tier_str: String, cooldown: u32) -> Result<()> {
+    fn add_reward(name: String, cost: u32, description: String, tier_str: String, cooldown: u32, fixed_price: bool) -> Result<()> {
         use colored::Colorize;
         use crate::progression::RewardTier;
 
-        let data_dir = get_data_dir()?;
-        let shop_path = data_dir.join("shop.json");
-
-        let mut shop = RewardStore::load(&shop_path)?;
+        let mut profile = Profile::open_active()?;
 
         // Parse tier
-        let tier = match tier_str.to_lowercase().as_str() {
-            "normal" => RewardTier::Normal,
-            "heroic" => RewardTier::Heroic,
-            "epic" => RewardTier::Epic,
-            "legendary" => RewardTier::Legendary,
-            _ => return Err(anyhow::anyhow!("Invalid tier. Use: normal, heroic, epic, or legendary")),
-        };
+        let tier = RewardTier::from_str(&tier_str)?;
 
         // Add the reward
-        let reward_id = shop.add_reward(name.clone(), cost, description.clone(), tier, cooldown);
+        let reward_id = profile.shop.add_reward(name.clone(), cost, description.clone(), tier, cooldown);
+        if fixed_price {
+            if let Some(reward) = profile.shop.rewards.iter_mut().find(|r| r.id == reward_id) {
+                reward.fixed_price = true;
+            }
+        }
 
         // Save changes
-        safe_write(&shop_path, &shop)?;
+        profile.save()?;
 
         // Display success
         println!();
@@ -475,46 +1004,703 @@ impl CLI {
         Ok(())
     }
 
-    fn remove_reward(reward_identifier: String) -> Result<()> {
+    fn handle_reward(action: RewardAction) -> Result<()> {
         use colored::Colorize;
+        use crate::progression::RewardTier;
+        use crate::shop::RewardEdit;
 
-        let data_dir = get_data_dir()?;
-        let shop_path = data_dir.join("shop.json");
+        let mut profile = Profile::open_active()?;
 
-        let mut shop = RewardStore::load(&shop_path)?;
+        match action {
+            RewardAction::List => {
+                println!();
+                for reward in &profile.shop.rewards {
+                    let line = format!("[ID: {}] {} - {} Gold, {}{}{}",
+                        reward.id,
+                        reward.name,
+                        reward.cost,
+                        reward.tier.name(),
+                        if reward.cooldown_hours > 0 { format!(", {}h cooldown", reward.cooldown_hours) } else { String::new() },
+                        if reward.fixed_price { ", fixed price" } else { "" }
+                    );
+                    if reward.hidden {
+                        println!("{} {}", line.dimmed(), "(hidden)".dimmed());
+                    } else {
+                        println!("{}", line);
+                    }
+                }
+                println!();
+            }
+            RewardAction::Edit { reward, name, cost, description, tier, cooldown, fixed_price } => {
+                let id = profile.shop.find_reward_id(&reward)?;
+                let edit = RewardEdit {
+                    name,
+                    description,
+                    cost,
+                    tier: tier.map(|t| RewardTier::from_str(&t)).transpose()?,
+                    cooldown_hours: cooldown,
+                    fixed_price,
+                };
+                profile.shop.edit_reward(id, edit)?;
+                profile.save()?;
+
+                let reward = profile.shop.get_reward(id).context("Reward not found")?;
+                println!("✅ Updated [ID: {}] {} - {} Gold, {}, {}h cooldown",
+                    reward.id, reward.name, reward.cost, reward.tier.name(), reward.cooldown_hours);
+            }
+            RewardAction::Hide { reward } => {
+                let id = profile.shop.find_reward_id(&reward)?;
+                profile.shop.set_hidden(id, true)?;
+                profile.save()?;
+                println!("🙈 Hidden from the shop. Undo with 'taskquest reward unhide {}'", id);
+            }
+            RewardAction::Unhide { reward } => {
+                let id = profile.shop.find_reward_id(&reward)?;
+                profile.shop.set_hidden(id, false)?;
+                profile.save()?;
+                println!("✅ Back in the shop");
+            }
+        }
 
-        // Try to parse as ID first, otherwise search by name
-        let reward_id = if let Ok(id) = reward_identifier.parse::<u32>() {
-            id
-        } else {
-            shop.get_reward_by_name(&reward_identifier)
-                .map(|r| r.id)
-                .context(format!("Reward '{}' not found", reward_identifier))?
-        };
+        Ok(())
+    }
 
-        // Get reward name before removing
-        let reward_name = shop.get_reward(reward_id)
-            .context("Reward not found")?
-            .name.clone();
+    fn handle_goal(action: GoalAction) -> Result<()> {
+        use colored::Colorize;
+        use crate::shop::{SavingsGoal, SavingsRule};
 
-        // Remove the reward
-        shop.remove_reward(reward_id)?;
+        let mut profile = Profile::open_active()?;
+        let ctx = profile.price_context();
+        let Profile { character, shop, .. } = &mut profile;
 
-        // Save changes
-        safe_write(&shop_path, &shop)?;
+        match action {
+            GoalAction::List => {
+                if character.savings_goals.is_empty() {
+                    println!("No savings goals. Start one with 'taskquest goal set <reward> --percent 20'");
+                    return Ok(());
+                }
+
+                println!();
+                for goal in &character.savings_goals {
+                    println!("🎯 [ID: {}] {}  [{}] {}/{} Gold ({})",
+                        goal.reward_id,
+                        goal.reward_name.bold(),
+                        goal.bar(20).yellow(),
+                        goal.reserved,
+                        goal.target,
+                        goal.rule.describe()
+                    );
+                    if goal.is_complete() {
+                        println!("   ✨ Fully saved! Buy it with 'taskquest buy {}'", goal.reward_id);
+                    }
+                }
+                println!();
+                println!("Reserved: {} of {} Gold", character.reserved_gold(), character.gold);
+                println!();
+            }
+            GoalAction::Set { reward, percent, amount } => {
+                let id = shop.find_reward_id(&reward)?;
+                let reward = shop.get_reward(id).context("Reward not found")?;
+                let rule = match (percent, amount) {
+                    (Some(percent), _) => SavingsRule::Percent(percent),
+                    (None, Some(amount)) => SavingsRule::Fixed(amount),
+                    (None, None) => anyhow::bail!("Set --percent or --amount"),
+                };
+
+                // Saving starts from the price as it stands today
+                let target = shop.cost_of(reward, &ctx);
+
+                match character.savings_goals.iter_mut().find(|g| g.reward_id == id) {
+                    Some(goal) => {
+                        goal.rule = rule;
+                        goal.target = target;
+                    }
+                    None => character.savings_goals.push(SavingsGoal {
+                        reward_id: id,
+                        reward_name: reward.name.clone(),
+                        target,
+                        rule,
+                        reserved: 0,
+                    }),
+                }
+                let name = reward.name.clone();
+                profile.save()?;
+                println!("🎯 Saving for {} ({} Gold): {}", name.bold(), target, rule.describe());
+            }
+            GoalAction::Release { reward, amount } => {
+                let id = shop.find_reward_id(&reward)?;
+                let released = character.release_goal(id, amount)?;
+                profile.save()?;
+                if amount.is_some() {
+                    println!("✅ Released {} gold; the goal keeps saving", released);
+                } else {
+                    println!("✅ Goal removed, {} gold released", released);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn remove_reward(reward_identifier: String, force: bool) -> Result<()> {
+        use colored::Colorize;
+
+        let mut profile = Profile::open_active()?;
+        let removed = profile.remove_reward(&reward_identifier, force)?;
+        profile.save()?;
+
+        // Gold saved for it becomes spendable again
+        if let Some(released) = removed.released_gold {
+            println!("🎯 Savings goal removed, {} gold released", released);
+        }
 
         // Display success
         println!();
         println!("{}", "╔════════════════════════════════════════╗".yellow());
         println!("{}", "║      🗑️  REWARD REMOVED  🗑️            ║".yellow().bold());
         println!("{}", "╠════════════════════════════════════════╣".yellow());
-        println!("║ Removed: {}                            ║", reward_name.bold());
+        println!("║ Removed: {}                            ║", removed.reward_name.bold());
+        println!("{}", "╚════════════════════════════════════════╝".yellow());
+        println!();
+
+        Ok(())
+    }
+
+    fn suggest_challenge(
+        description: String,
+        project: Option<String>,
+        tags: Vec<String>,
+        priority: Option<String>,
+        estimate: Option<String>,
+    ) -> Result<()> {
+        use colored::Colorize;
+        use crate::taskwarrior::challenge;
+
+        if let Some(ref estimate) = estimate {
+            if challenge::parse_estimate(estimate).is_none() {
+                anyhow::bail!("Invalid estimate: {}. Use e.g. 45min, 2h or PT1H30M", estimate);
+            }
+        }
+
+        let task = TaskData {
+            status: "pending".to_string(),
+            description,
+            project,
+            tags,
+            priority,
+            estimate,
+            ..Default::default()
+        };
+        let profile = Profile::open_active()?;
+        let suggestion = profile.suggest_challenge(&task);
+
+        println!();
+        println!("🎲 Suggested challenge for \"{}\": {}", task.description, suggestion.challenge.to_string().bold());
+        println!();
+        println!("   {:>5}  {}", format!("{:.1}", suggestion.base), suggestion.base_reason);
+        for (reason, adjust) in &suggestion.adjustments {
+            println!("   {:>5}  {}", format!("{:+}", adjust), reason);
+        }
+        println!();
+
+        Ok(())
+    }
+
+    fn handle_rest(action: RestAction) -> Result<()> {
+        let mut profile = Profile::open_active()?;
+        let today = chrono::Utc::now().date_naive();
+
+        match action {
+            RestAction::Start { until } => {
+                let until = until
+                    .map(|s| chrono::NaiveDate::parse_from_str(&s, "%Y-%m-%d")
+                        .with_context(|| format!("Invalid date: {}. Use YYYY-MM-DD", s)))
+                    .transpose()?;
+                let period = profile.character.start_rest(today, until)?.clone();
+                profile.save()?;
+                match period.end {
+                    Some(end) => println!("🏖️  Resting through {}. Streaks and habits are paused.", end),
+                    None => println!("🏖️  Resting. Streaks and habits are paused until 'taskquest rest end'."),
+                }
+            }
+            RestAction::End => {
+                let days = profile.character.end_rest(today)?;
+                profile.save()?;
+                println!("⚔️  Welcome back! Rested {} day(s); today counts again.", days);
+            }
+            RestAction::Status => Formatter::print_rest(&profile.character, &profile.config.rest, today),
+        }
+
+        Ok(())
+    }
+
+    fn reconcile(dry_run: bool, since: Option<String>, file: Option<std::path::PathBuf>, verbose: bool) -> Result<()> {
+        use crate::taskwarrior::{reconcile, TaskwarriorIntegration};
+
+        let since = since
+            .map(|s| chrono::NaiveDate::parse_from_str(&s, "%Y-%m-%d")
+                .with_context(|| format!("Invalid date: {}. Use YYYY-MM-DD", s)))
+            .transpose()?
+            .map(|d| d.and_time(chrono::NaiveTime::MIN).and_utc());
+
+        let tasks = match file {
+            Some(path) => reconcile::read_export(&path)?,
+            None => reconcile::export_completed()?,
+        };
+        TaskwarriorIntegration::reconcile(tasks, since, dry_run, verbose)
+    }
+
+    fn explain(uuid: String) -> Result<()> {
+        let profile = Profile::open_active()?;
+        Formatter::print_explain(profile.history.find(&uuid)?);
+
+        Ok(())
+    }
+
+    fn calibration() -> Result<()> {
+        use crate::history::calibration;
+
+        let profile = Profile::open_active()?;
+        let (overall, projects) = calibration::calibrate(&profile.config.calibration, &profile.history);
+        Formatter::print_calibration(&overall, &projects);
+
+        Ok(())
+    }
+
+    fn focus(task_id: String, minutes: Option<u32>) -> Result<()> {
+        use crate::focus::timer;
+        use crate::taskwarrior::export;
+        use std::time::Duration;
+
+        let task = export::find(&task_id)?;
+        if task.status != "pending" {
+            anyhow::bail!("Task {} is {}; focus sessions need a pending task", task_id, task.status);
+        }
+
+        let profile = Profile::open_active()?;
+        let planned = minutes.unwrap_or(profile.config.focus.default_minutes).max(1);
+        Formatter::print_focus_start(&profile.character, &task, planned);
+
+        let started_at = chrono::Utc::now();
+        let length = Duration::from_secs(planned as u64 * 60);
+        let elapsed = timer::run(length, |elapsed| {
+            print!("\r{}", Formatter::focus_progress(elapsed, length));
+            let _ = io::stdout().flush();
+        });
+        println!("\r{}", Formatter::focus_progress(elapsed, length));
+
+        // Reload: quests may have been credited while the timer ran
+        let mut profile = Profile::open(profile.data_dir())?;
+        let spent = (elapsed.as_secs() / 60) as u32;
+        match profile.record_focus_session(&task, planned, spent, started_at) {
+            Some(outcome) => {
+                profile.save()?;
+                Events::emit(&profile.config.events, &profile.character.name, &outcome.events());
+                Formatter::print_focus_result(&outcome);
+            }
+            None => {
+                println!();
+                println!("⏹️  Stopped after {} min; sessions under {} min don't count",
+                    spent, profile.config.focus.min_minutes);
+                println!();
+            }
+        }
+
+        Ok(())
+    }
+
+    fn deep_work(days: i64, file: Option<std::path::PathBuf>) -> Result<()> {
+        use colored::Colorize;
+        use crate::timewarrior;
+
+        let mut profile = Profile::open_active()?;
+        match file {
+            Some(path) => profile.set_intervals(timewarrior::read_export(&path)?),
+            None if !profile.config.timewarrior.enabled => anyhow::bail!(
+                "Timewarrior integration is off. Set \"timewarrior\": {{ \"enabled\": true }} in config.json or use --file"
+            ),
+            None => {}
+        }
+
+        let today = chrono::Utc::now().date_naive();
+        let mut focus_days = Vec::new();
+        let mut unlocked = Vec::new();
+        for offset in (0..days.max(1)).rev() {
+            let day = today - chrono::Duration::days(offset);
+            if let Some((focus, achievements)) = profile.record_focus_day(day) {
+                focus_days.push((day, focus));
+                unlocked.extend(achievements);
+            }
+        }
+        profile.save()?;
+
+        Formatter::print_deep_work(&focus_days);
+        for achievement in unlocked {
+            println!("🏆 Achievement unlocked: {} {}", achievement.icon, achievement.title.bold());
+        }
+
+        Ok(())
+    }
+
+    fn habits() -> Result<()> {
+        use crate::history::habits;
+        use crate::taskwarrior::export;
+
+        let profile = Profile::open_active()?;
+        // Templates add the habits that haven't been completed yet
+        let templates = export::export(&["status:recurring"]).unwrap_or_else(|e| {
+            eprintln!("Warning: Failed to read recurring tasks: {}", e);
+            Vec::new()
+        });
+        let today = chrono::Utc::now().date_naive();
+        let habits = habits::habits(&profile.config.habits, &profile.rest_days(), &profile.history, &templates, today);
+        Formatter::print_habits(&habits, &profile.config.habits);
+
+        Ok(())
+    }
+
+    fn report(period: String, by: String, json: bool) -> Result<()> {
+        use crate::history::{Report, ReportGrouping, ReportPeriod};
+
+        let period = ReportPeriod::from_str(&period)?;
+        let grouping = ReportGrouping::from_str(&by)?;
+
+        let profile = Profile::open_active()?;
+        let report = Report::build(&profile.history, period, grouping, chrono::Utc::now());
+
+        if json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            Formatter::print_report(&report, &by);
+        }
+
+        Ok(())
+    }
+
+    fn activity(metric: String, days: i64, mono: bool) -> Result<()> {
+        use colored::Colorize;
+        use crate::display::charts::Charts;
+        use crate::history::ActivityMetric;
+
+        let metric = ActivityMetric::from_str(&metric)?;
+        let mono = mono || !colored::control::SHOULD_COLORIZE.should_colorize();
+
+        let profile = Profile::open_active()?;
+        let history = &profile.history;
+        let tracker = &profile.achievements;
+
+        // Days active before history was recorded still show up, at the lowest intensity
+        let mut totals = history.daily_totals(metric);
+        for day in &tracker.progress.active_days {
+            if let Ok(date) = chrono::NaiveDate::parse_from_str(day, "%Y-%m-%d") {
+                totals.entry(date).or_insert(1);
+            }
+        }
+
+        let today = chrono::Utc::now().date_naive();
+        let label = match metric {
+            ActivityMetric::Xp => "XP",
+            ActivityMetric::Tasks => "quests",
+        };
+
+        println!();
+        println!("{}", format!("Activity over the last year ({})", label).bold());
+        println!();
+        for line in Charts::heatmap(&totals, today, mono) {
+            println!("{}", line);
+        }
+        println!("    {}", Charts::legend(mono));
+        println!();
+
+        // XP per day
+        let daily_xp = history.daily_totals(ActivityMetric::Xp);
+        let xp_values: Vec<u32> = (0..days.max(1))
+            .rev()
+            .map(|ago| daily_xp.get(&(today - chrono::Duration::days(ago))).copied().unwrap_or(0))
+            .collect();
+        let xp_line = Charts::sparkline(&xp_values);
+        println!("XP/day ({}d):   {}  max {}",
+            days.max(1),
+            if mono { xp_line } else { xp_line.green().to_string() },
+            xp_values.iter().max().unwrap_or(&0)
+        );
+
+        // Level at the end of each week, carrying the last known level forward
+        let levels = history.level_by_day();
+        let first_level = levels.values().next().copied().unwrap_or(1);
+        let level_values: Vec<u32> = (0..52)
+            .rev()
+            .map(|weeks_ago| {
+                let week_end = today - chrono::Duration::weeks(weeks_ago);
+                levels.range(..=week_end).next_back()
+                    .map(|(_, level)| *level)
+                    .unwrap_or(first_level)
+            })
+            .collect();
+        let level_line = Charts::sparkline(&level_values);
+        println!("Level (52w):   {}  {} → {}",
+            if mono { level_line } else { level_line.cyan().to_string() },
+            level_values.first().unwrap_or(&1),
+            level_values.last().unwrap_or(&1)
+        );
+        println!();
+
+        Ok(())
+    }
+
+    fn handle_title(action: TitleAction) -> Result<()> {
+        use colored::Colorize;
+        use crate::achievements::Title;
+
+        let mut profile = Profile::open_active()?;
+
+        match action {
+            TitleAction::List => {
+                println!();
+                println!("{}", "╔════════════════════════════════════════════════════╗".yellow());
+                println!("{}", "║                🎖️  TITLES  🎖️                       ║".yellow().bold());
+                println!("{}", "╚════════════════════════════════════════════════════╝".yellow());
+                println!();
+
+                let character = &profile.character;
+                for title in Title::all() {
+                    let active = character.active_title.as_deref() == Some(title.name);
+                    if profile.achievements.is_title_unlocked(title, character) {
+                        println!("{} {} [{}]{}",
+                            "✓".green(),
+                            title.name.yellow().bold(),
+                            title.id.cyan(),
+                            if active { " (active)".green().to_string() } else { String::new() }
+                        );
+                    } else {
+                        println!("{} {} - {}",
+                            "✗".red(),
+                            title.name.dimmed(),
+                            title.requirement().dimmed()
+                        );
+                    }
+                }
+                println!();
+                println!("Use 'taskquest title set <id or name>' to display a title");
+                println!();
+            }
+            TitleAction::Set { title } => {
+                profile.set_title(&title)?;
+                profile.save()?;
+
+                println!("✅ Now known as: {}", profile.character.display_name());
+            }
+            TitleAction::Clear => {
+                profile.character.active_title = None;
+                profile.save()?;
+
+                println!("✅ Active title cleared");
+            }
+        }
+
+        Ok(())
+    }
+
+    fn prestige(skip_confirm: bool) -> Result<()> {
+        use colored::Colorize;
+
+        let mut profile = Profile::open_active()?;
+
+        // Nothing is saved until confirmed, so a character below the
+        // prestige level is refused before the question is asked
+        let record = profile.character.prestige()?.clone();
+
+        if !skip_confirm {
+            println!("Prestige will reset {} to Level 1 with 0 XP.", profile.character.name);
+            println!("Stats, gold and achievements are kept, and every future quest");
+            println!("earns +{}% XP and gold.", PrestigeSystem::bonus_percent(record.rank));
+            print!("\nAscend to prestige rank {}? (y/N) ", record.rank);
+            io::stdout().flush()?;
+            let mut answer = String::new();
+            io::stdin().read_line(&mut answer)?;
+            if !answer.trim().eq_ignore_ascii_case("y") {
+                println!("Prestige cancelled.");
+                return Ok(());
+            }
+        }
+
+        crate::storage::backups::Backups::create(profile.data_dir(), "before prestige", &profile.config.backup)?;
+        profile.save()?;
+
+        println!();
+        println!("{}", "╔════════════════════════════════════════╗".yellow());
+        println!("{}", "║        🌟 PRESTIGE ACHIEVED! 🌟        ║".yellow().bold());
+        println!("{}", "╠════════════════════════════════════════╣".yellow());
+        println!("║ Rank: {}                                ║", PrestigeSystem::stars(record.rank).yellow().bold());
+        println!("║ Previous run: Level {} ({} XP)        ║", record.level, record.total_xp);
+        println!("║ Permanent bonus: +{}% XP/Gold          ║", PrestigeSystem::bonus_percent(record.rank));
         println!("{}", "╚════════════════════════════════════════╝".yellow());
         println!();
 
         Ok(())
     }
 
+    fn handle_backup(action: BackupAction) -> Result<()> {
+        use colored::Colorize;
+        use crate::storage::backups::Backups;
+
+        let data_dir = get_data_dir()?;
+        let config = Config::load(&data_dir)?.backup;
+
+        match action {
+            BackupAction::List => {
+                let snapshots = Backups::list(&data_dir)?;
+                if snapshots.is_empty() {
+                    println!("No snapshots yet. Create one with 'taskquest backup create'");
+                    return Ok(());
+                }
+
+                println!();
+                for snapshot in snapshots {
+                    let summary = Profile::open(&snapshot.dir(&data_dir))
+                        .map(|p| format!("Level {}, {} XP, {} Gold", p.character.level, p.character.total_xp, p.character.gold))
+                        .unwrap_or_else(|_| "unreadable character".to_string());
+                    let created = snapshot.created_at()
+                        .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+                        .unwrap_or_else(|| snapshot.created_at.clone());
+
+                    println!("{}  {}  {}  {}",
+                        snapshot.id.cyan().bold(),
+                        created,
+                        summary,
+                        format!("({})", snapshot.reason).dimmed()
+                    );
+                }
+                println!();
+                println!("Restore with 'taskquest backup restore <id>'");
+                println!();
+            }
+            BackupAction::Create => {
+                let snapshot = Backups::create(&data_dir, "manual", &config)?;
+                println!("✅ Snapshot {} created ({} files)", snapshot.id, snapshot.files.len());
+            }
+            BackupAction::Restore { id, yes } => {
+                let snapshot = Backups::find(&data_dir, &id)?;
+
+                if !yes {
+                    println!("This replaces {} with the copies from snapshot {}.",
+                        crate::storage::backups::SNAPSHOT_FILES.join(", "),
+                        snapshot.id
+                    );
+                    println!("The current data is snapshotted first.");
+                    print!("Restore? (y/N) ");
+                    io::stdout().flush()?;
+                    let mut answer = String::new();
+                    io::stdin().read_line(&mut answer)?;
+                    if !answer.trim().eq_ignore_ascii_case("y") {
+                        println!("Restore cancelled.");
+                        return Ok(());
+                    }
+                }
+
+                Backups::restore(&data_dir, &id, &config)?;
+                println!("✅ Restored snapshot {}", id);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn doctor(fix: bool) -> Result<()> {
+        use crate::doctor::Doctor;
+
+        let data_dir = get_data_dir()?;
+        let findings = Doctor::run(&data_dir, fix)?;
+
+        Formatter::print_doctor(&findings, fix);
+
+        let outstanding = findings.iter().filter(|f| f.is_outstanding()).count();
+        if outstanding > 0 {
+            anyhow::bail!("{} problem(s) remaining", outstanding);
+        }
+
+        Ok(())
+    }
+
+    fn handle_profile(action: ProfileAction) -> Result<()> {
+        use colored::Colorize;
+        use crate::storage::profiles::{self, ProfileConfig, ProfileRule};
+
+        let base_dir = profiles::base_dir()?;
+
+        match action {
+            ProfileAction::List => {
+                let active = profiles::active_profile(&base_dir)?;
+                let config = ProfileConfig::load(&base_dir)?;
+
+                println!();
+                for name in profiles::list(&base_dir)? {
+                    let dir = profiles::profile_dir(&base_dir, &name);
+                    let summary = Profile::open(&dir)
+                        .map(|p| format!("{} - {}, Level {}", p.character.display_name(), p.character.class, p.character.level))
+                        .unwrap_or_else(|_| "no character yet".to_string());
+
+                    let marker = if name == active { "*".green().bold() } else { " ".normal() };
+                    println!("{} {} - {}", marker, name.cyan().bold(), summary);
+
+                    for rule in config.rules.iter().filter(|r| r.profile == name) {
+                        if let Some(ref prefix) = rule.project_prefix {
+                            println!("     project:{}*", prefix);
+                        }
+                        if let Some(ref location) = rule.task_data {
+                            println!("     data:{}", location);
+                        }
+                    }
+                }
+                println!();
+            }
+            ProfileAction::Create { name, project_prefix, task_data } => {
+                profiles::create(&base_dir, &name)?;
+
+                if project_prefix.is_some() || task_data.is_some() {
+                    let mut config = ProfileConfig::load(&base_dir)?;
+                    config.rules.push(ProfileRule {
+                        profile: name.clone(),
+                        project_prefix,
+                        task_data,
+                    });
+                    config.save(&base_dir)?;
+                }
+
+                println!("✅ Profile '{}' created", name);
+                println!("Create its character with 'taskquest --profile {} init'", name);
+            }
+            ProfileAction::Delete { name, yes } => {
+                if !yes {
+                    print!("Delete profile '{}' and all of its data? (y/N) ", name);
+                    io::stdout().flush()?;
+                    let mut answer = String::new();
+                    io::stdin().read_line(&mut answer)?;
+                    if !answer.trim().eq_ignore_ascii_case("y") {
+                        println!("Deletion cancelled.");
+                        return Ok(());
+                    }
+                }
+
+                profiles::delete(&base_dir, &name)?;
+                println!("🗑️  Profile '{}' deleted", name);
+            }
+            ProfileAction::Switch { name } => {
+                if !profiles::exists(&base_dir, &name)? {
+                    anyhow::bail!("Profile '{}' does not exist", name);
+                }
+
+                let mut config = ProfileConfig::load(&base_dir)?;
+                config.default = Some(name.clone());
+                config.save(&base_dir)?;
+
+                println!("✅ Default profile set to '{}'", name);
+            }
+        }
+
+        Ok(())
+    }
+
     fn handle_sync(action: SyncAction) -> Result<()> {
         use crate::sync::GitSync;
 
@@ -1,5 +1,15 @@
-use colored::Colorize;
-use crate::character::Character;
+use colored::{ColoredString, Colorize};
+use crate::character::{Character, PrestigeSystem, RestConfig};
+use crate::progression::{Breakdown, Effect, LootRoll, RewardBreakdown, StatBonuses};
+use crate::history::CompletionRecord;
+use crate::history::calibration::{Calibration, Verdict};
+use crate::history::habits::{Habit, HabitConfig};
+use crate::history::report::{Report, ReportRow};
+use crate::doctor::{Finding, Severity};
+use crate::shop::budgets::PurchaseRecord;
+use crate::timewarrior::{DailyFocus, DEEP_WORK_MINUTES};
+use crate::game::FocusOutcome;
+use crate::taskwarrior::TaskData;
 
 pub struct Formatter;
 
@@ -10,18 +20,18 @@ impl Formatter {
 
         println!();
         println!("{}", "╔════════════════════════════════════════════════════════════════╗".cyan());
-        println!("{}", format!("║ {} - {} {}, Level {} ║",
-            character.name,
+        println!("{}", format!("║ {} - {}, Level {} ║",
+            character.display_name(),
             character.class,
-            if let Some(title) = &character.active_title {
-                format!("\"{}\"", title)
-            } else {
-                String::new()
-            },
             character.level
         ).cyan().bold());
         println!("{}", "╠════════════════════════════════════════════════════════════════╣".cyan());
 
+        // Prestige stars crown the avatar
+        if character.prestige_rank > 0 {
+            println!("║ {:^62} ║", PrestigeSystem::stars(character.prestige_rank).yellow().bold());
+        }
+
         // Display avatar
         for line in avatar.lines() {
             if !line.trim().is_empty() {
@@ -41,11 +51,24 @@ impl Formatter {
             character.total_xp + character.xp_to_next_level(),
             character.xp_progress_percent()
         );
-        println!("║   CON: {:>3}        │  Gold: {} 💰                            ║",
-            character.stats.constitution.floor() as u16, character.gold);
-        println!("║   INT: {:>3}        │                                         ║",
-            character.stats.intelligence.floor() as u16
-        );
+        if character.reserved_gold() > 0 {
+            println!("║   CON: {:>3}        │  Gold: {} 💰 ({} reserved)               ║",
+                character.stats.constitution.floor() as u16, character.gold, character.reserved_gold());
+        } else {
+            println!("║   CON: {:>3}        │  Gold: {} 💰                            ║",
+                character.stats.constitution.floor() as u16, character.gold);
+        }
+        if character.prestige_rank > 0 {
+            println!("║   INT: {:>3}        │  Prestige: {} (+{}% XP/Gold)            ║",
+                character.stats.intelligence.floor() as u16,
+                character.prestige_rank,
+                PrestigeSystem::bonus_percent(character.prestige_rank)
+            );
+        } else {
+            println!("║   INT: {:>3}        │                                         ║",
+                character.stats.intelligence.floor() as u16
+            );
+        }
         println!("║   WIS: {:>3}        │  Next Level: {} XP                      ║",
             character.stats.wisdom.floor() as u16, character.xp_to_next_level());
         println!("║   CHA: {:>3}        │  Tasks Completed: {} ✓                  ║",
@@ -59,11 +82,21 @@ impl Formatter {
         let bar: String = "█".repeat(filled) + &"░".repeat(bar_width - filled);
         println!("║ XP Progress: [{}] ║", bar.green());
 
+        // Savings goals
+        for goal in &character.savings_goals {
+            println!("║ 🎯 {}: [{}] {}/{} ║",
+                goal.reward_name,
+                goal.bar(30).yellow(),
+                goal.reserved,
+                goal.target
+            );
+        }
+
         println!("{}", "╚════════════════════════════════════════════════════════════════╝".cyan());
         println!();
     }
 
-    pub fn print_stats(character: &Character) {
+    pub fn print_stats(character: &Character, bonuses: &StatBonuses) {
         // Helper function to create a stat bar
         fn stat_bar(value: u16, max: u16) -> String {
             let bar_width = 20;
@@ -114,6 +147,15 @@ impl Formatter {
         println!("║   Wisdom:       {:>3} [{}] ║", wis_val, stat_bar(wis_val, *max_stat));
         println!("║   Charisma:     {:>3} [{}] ║", cha_val, stat_bar(cha_val, *max_stat));
         println!("{}", "╠══════════════════════════════════════════════════════╣".yellow());
+        println!("║ ABILITY EFFECTS:                                     ║");
+        println!("║   STR: +{:.1}% XP on challenge {}+ quests             ║",
+            (bonuses.high_challenge_multiplier - 1.0) * 100.0, bonuses.high_challenge_min);
+        println!("║   DEX: +{:.1}% loot drop chance                      ║", bonuses.loot_chance_bonus * 100.0);
+        println!("║   CON: Late penalty softened by {:.0}%               ║", bonuses.late_penalty_relief * 100.0);
+        println!("║   INT: +{:.1}% XP on all quests                      ║", (bonuses.xp_multiplier - 1.0) * 100.0);
+        println!("║   WIS: Streak survives {} missed day(s)              ║", bonuses.streak_grace_days);
+        println!("║   CHA: {:.1}% off shop prices                        ║", bonuses.shop_discount * 100.0);
+        println!("{}", "╠══════════════════════════════════════════════════════╣".yellow());
         println!("║ PROGRESSION:                                         ║");
         println!("║   Total XP:     {:>6}                                ║", character.total_xp);
         println!("║   Current XP:   {:>6}                                ║",
@@ -129,7 +171,436 @@ impl Formatter {
 
         println!("║   Total Gold:   {:>6} 💰                             ║", character.gold);
         println!("║   Tasks Done:   {:>6} ✓                              ║", character.tasks_completed);
+
+        if character.prestige_rank > 0 {
+            println!("{}", "╠══════════════════════════════════════════════════════╣".yellow());
+            println!("║ PRESTIGE: {} (+{}% XP/Gold)                          ║",
+                PrestigeSystem::stars(character.prestige_rank).yellow().bold(),
+                PrestigeSystem::bonus_percent(character.prestige_rank)
+            );
+            println!("║   Lifetime XP:  {:>6}                                ║", character.lifetime_xp());
+            for record in &character.prestige_history {
+                let date = record.date.get(..10).unwrap_or(&record.date);
+                println!("║   Rank {}: {} at Level {} ({} XP, {} tasks)        ║",
+                    record.rank, date, record.level, record.total_xp, record.tasks_completed);
+            }
+        }
         println!("{}", "╚══════════════════════════════════════════════════════╝".yellow());
         println!();
     }
+
+    pub fn print_report(report: &Report, grouping: &str) {
+        fn row(r: &ReportRow) -> String {
+            let on_time = r.on_time_rate()
+                .map(|rate| format!("{:.0}%", rate * 100.0))
+                .unwrap_or_else(|| "-".to_string());
+            format!("{:<16} {:>7} {:>8} {:>7} {:>6} {:>8}",
+                r.key, r.completions, r.xp, r.gold, r.loot_drops, on_time)
+        }
+
+        println!();
+        println!("{}", "╔════════════════════════════════════════════════════════════╗".cyan());
+        println!("{}", "║                   📊  PROGRESS REPORT  📊                  ║".cyan().bold());
+        println!("{}", "╚════════════════════════════════════════════════════════════╝".cyan());
+        println!("{} → {}",
+            report.since.get(..10).unwrap_or(&report.since),
+            report.until.get(..10).unwrap_or(&report.until)
+        );
+        println!();
+
+        if report.rows.is_empty() {
+            println!("No quests completed in this period.");
+            println!();
+            return;
+        }
+
+        println!("{}", format!("{:<16} {:>7} {:>8} {:>7} {:>6} {:>8}",
+            grouping.to_uppercase(), "QUESTS", "XP", "GOLD", "LOOT", "ON TIME").bold());
+        println!("{}", "─".repeat(57));
+        for r in &report.rows {
+            println!("{}", row(r));
+        }
+        println!("{}", "─".repeat(57));
+        println!("{}", row(&report.total).bold());
+        if grouping.eq_ignore_ascii_case("stat") {
+            println!("{}", "Quests count under both stats they train, so rows add up to more than the total.".dimmed());
+        }
+        println!();
+    }
+
+    pub fn print_calibration(overall: &Calibration, projects: &[Calibration]) {
+        fn row(c: &Calibration) -> String {
+            let name = c.project.as_deref().unwrap_or("(all)");
+            format!("{:<16} {:>6} {:>9} {:>9} {:>7}",
+                name,
+                c.samples,
+                format!("{:.0} min", c.median_minutes),
+                format!("{:.1}", c.median_challenge),
+                format!("{:+.1}", c.offset))
+        }
+        fn verdict(c: &Calibration) -> ColoredString {
+            match c.verdict {
+                Verdict::NotEnoughData => "not enough data".dimmed(),
+                Verdict::Calibrated => "✓ calibrated".green(),
+                Verdict::RatedTooHigh => "⚠ rated too high".yellow(),
+                Verdict::RatedTooLow => "⚠ rated too low".yellow(),
+            }
+        }
+
+        println!();
+        println!("{}", "╔════════════════════════════════════════════════════════════╗".cyan());
+        println!("{}", "║               📏  CHALLENGE CALIBRATION  📏                ║".cyan().bold());
+        println!("{}", "╚════════════════════════════════════════════════════════════╝".cyan());
+        println!();
+
+        if overall.samples == 0 {
+            println!("No timed quests yet. Use 'task <id> start' before working on a task");
+            println!("and TaskQuest will compare how long it took with its challenge.");
+            println!();
+            return;
+        }
+
+        println!("{}", format!("{:<16} {:>6} {:>9} {:>9} {:>7}",
+            "PROJECT", "TIMED", "MEDIAN", "CHALLENGE", "OFFSET").bold());
+        println!("{}", "─".repeat(51));
+        for c in projects {
+            println!("{}  {}", row(c), verdict(c));
+        }
+        println!("{}", "─".repeat(51));
+        println!("{}  {}", row(overall).bold(), verdict(overall));
+        println!();
+        println!("{}", "Offset is the declared challenge minus the challenge the time spent suggests.".dimmed());
+        println!();
+    }
+
+    pub fn print_focus_start(character: &Character, task: &TaskData, minutes: u32) {
+        println!();
+        for line in character.class.get_avatar(character.level).lines() {
+            if !line.trim().is_empty() {
+                println!("  {}", line);
+            }
+        }
+        println!();
+        println!("🍅 {} - {} minute focus session", task.description.bold(), minutes);
+        let stats: Vec<String> = [task.get_stat1(), task.get_stat2()].into_iter()
+            .flatten()
+            .map(|s| format!("{:?}", s))
+            .collect();
+        if !stats.is_empty() {
+            println!("   Training {}", stats.join(" / "));
+        }
+        println!("{}", "   Press Enter or Ctrl-C to stop early (partial credit)".dimmed());
+        println!();
+    }
+
+    /// One line of the focus timer: progress bar and time left
+    pub fn focus_progress(elapsed: std::time::Duration, length: std::time::Duration) -> String {
+        const WIDTH: usize = 30;
+        let progress = if length.is_zero() { 1.0 } else { elapsed.as_secs_f64() / length.as_secs_f64() };
+        let filled = ((progress * WIDTH as f64).round() as usize).min(WIDTH);
+        let left = length.saturating_sub(elapsed).as_secs();
+        format!("   [{}{}] {:02}:{:02} left ",
+            "█".repeat(filled).red(),
+            "░".repeat(WIDTH - filled),
+            left / 60,
+            left % 60)
+    }
+
+    pub fn print_focus_result(outcome: &FocusOutcome) {
+        let session = &outcome.session;
+        println!();
+        if session.completed {
+            println!("✅ Focus session complete! {} min", session.minutes);
+        } else {
+            println!("⏹️  Session stopped after {} of {} min (partial credit)", session.minutes, session.planned_minutes);
+        }
+        println!("   {} XP", format!("+{}", session.xp).green().bold());
+        if outcome.leveled_up() {
+            println!("⬆️  Level {} → {}", outcome.level_before, session.level_after);
+        }
+        for achievement in &outcome.achievements {
+            println!("🏆 Achievement unlocked: {} {}", achievement.icon, achievement.title.bold());
+        }
+        println!();
+    }
+
+    pub fn print_deep_work(days: &[(chrono::NaiveDate, DailyFocus)]) {
+        fn hours(minutes: u32) -> String {
+            format!("{}h {:02}m", minutes / 60, minutes % 60)
+        }
+
+        println!();
+        println!("{}", "╔════════════════════════════════════════╗".cyan());
+        println!("{}", "║           🎧  DEEP WORK  🎧            ║".cyan().bold());
+        println!("{}", "╚════════════════════════════════════════╝".cyan());
+        println!();
+        println!("{}", format!("{:<12} {:>9} {:>13}", "DAY", "TRACKED", "LONGEST BLOCK").bold());
+        println!("{}", "─".repeat(36));
+        for (day, focus) in days {
+            let line = format!("{:<12} {:>9} {:>13}", day, hours(focus.minutes), hours(focus.longest_block));
+            if focus.minutes >= DEEP_WORK_MINUTES {
+                println!("{}  {}", line.green(), "✓ deep work".green());
+            } else {
+                println!("{}", line);
+            }
+        }
+        println!();
+    }
+
+    pub fn print_habits(habits: &[Habit], config: &HabitConfig) {
+        const WIDTH: usize = 10;
+
+        println!();
+        println!("{}", "╔════════════════════════════════════════════════════════════╗".cyan());
+        println!("{}", "║                      🔁  HABITS  🔁                        ║".cyan().bold());
+        println!("{}", "╚════════════════════════════════════════════════════════════╝".cyan());
+        println!();
+
+        if habits.is_empty() {
+            println!("No habits yet. Recurring tasks are tracked as habits, e.g.:");
+            println!("  task add Stretch recur:daily due:today");
+            println!();
+            return;
+        }
+
+        println!("{}", format!("{:<22} {:<10} {:<16} {:>6} {:>5}",
+            "HABIT", "EVERY", "STRENGTH", "STREAK", "BEST").bold());
+        println!("{}", "─".repeat(63));
+        for habit in habits {
+            let description: String = habit.description.chars().take(22).collect();
+            let filled = ((habit.standing.strength * WIDTH as f64).round() as usize).min(WIDTH);
+            println!("{:<22} {:<10} {}{} {:>4.0}% {:>6} {:>5}",
+                description,
+                habit.recur,
+                "█".repeat(filled).green(),
+                "░".repeat(WIDTH - filled),
+                habit.standing.strength * 100.0,
+                habit.standing.streak,
+                habit.standing.best_streak);
+
+            // Oldest period first; the last one is still open until it ends
+            let chart: String = habit.chart.iter().enumerate()
+                .map(|(i, done)| match (done, i + 1 == habit.chart.len()) {
+                    (Some(true), _) => "●".green().to_string(),
+                    (_, true) => "○".to_string(),
+                    (Some(false), false) => "·".dimmed().to_string(),
+                    (None, false) => " ".to_string(),
+                })
+                .collect();
+            if habit.recurrence.is_some() {
+                println!("{:<22} {}", "", chart);
+            } else {
+                println!("{:<22} {}", "", "unknown recurrence".dimmed());
+            }
+        }
+        println!();
+        println!("{}", "● done  · skipped  ○ not done yet  (blank: resting)".dimmed());
+        println!("{}", format!("Full strength adds {:.0}% XP and gold.", config.max_bonus * 100.0).dimmed());
+        println!();
+    }
+
+    /// Itemised XP, gold and loot roll, one line per step
+    pub fn reward_breakdown_lines(breakdown: &RewardBreakdown) -> Vec<String> {
+        let mut lines = vec!["XP".green().bold().to_string()];
+        lines.extend(Self::breakdown_lines(&breakdown.xp));
+        lines.push("Gold".yellow().bold().to_string());
+        lines.extend(Self::breakdown_lines(&breakdown.gold));
+        lines.push("Loot".magenta().bold().to_string());
+        lines.push(format!("  {}", Self::loot_roll_line(&breakdown.loot)));
+        lines
+    }
+
+    fn breakdown_lines(breakdown: &Breakdown) -> Vec<String> {
+        fn amount(value: f64) -> String {
+            if (value - value.round()).abs() < 0.05 {
+                format!("{:.0}", value)
+            } else {
+                format!("{:.1}", value)
+            }
+        }
+
+        let mut lines: Vec<String> = breakdown.lines.iter()
+            .map(|line| {
+                let effect = match line.effect {
+                    Effect::Base => String::new(),
+                    Effect::Multiply(factor) => format!("×{:.2}", factor),
+                    Effect::Add(bonus) => format!("+{}", bonus),
+                    Effect::Roll { min, max } => format!("{}–{}", min, max),
+                };
+                format!("  {:<36} {:>7} {:>7}", line.source, effect, amount(line.amount))
+            })
+            .collect();
+        lines.push(format!("  {:<36} {:>7} {:>7}", "total", "", breakdown.total()).bold().to_string());
+        lines
+    }
+
+    fn loot_roll_line(loot: &LootRoll) -> String {
+        let mut chance = format!("{:.0}% base", loot.base_chance * 100.0);
+        if loot.bonus_chance > 0.0 {
+            chance.push_str(&format!(" + {:.1}% DEX", loot.bonus_chance * 100.0));
+        }
+        if loot.multiplier != 1.0 {
+            chance = format!("({}) × {:.0}%", chance, loot.multiplier * 100.0);
+        }
+        let outcome = match loot.drop {
+            Some(ref drop) => drop.to_string(),
+            None => "nothing".to_string(),
+        };
+        format!("{:.0}% chance: {}, rolled {:.1} → {}", loot.chance * 100.0, chance, loot.roll * 100.0, outcome)
+    }
+
+    /// How a completed quest's rewards were worked out
+    pub fn print_explain(record: &CompletionRecord) {
+        println!();
+        println!("{}", "╔════════════════════════════════════════════════════════════╗".cyan());
+        println!("{}", "║                  🔍  REWARD BREAKDOWN  🔍                  ║".cyan().bold());
+        println!("{}", "╚════════════════════════════════════════════════════════════╝".cyan());
+        println!();
+        println!("{}", record.description.bold());
+        println!("  UUID:       {}", record.uuid);
+        if let Some(ref project) = record.project {
+            println!("  Project:    {}", project);
+        }
+        println!("  Completed:  {}", record.completed_at()
+            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| record.completed_at.clone()));
+        match record.xp_challenge {
+            Some(capped) => println!("  Challenge:  {} (rewarded as {}, capped by calibration)", record.challenge, capped),
+            None => println!("  Challenge:  {}", record.challenge),
+        }
+        println!("  Timing:     {}", record.timing.label());
+        println!();
+
+        match record.breakdown {
+            Some(ref breakdown) => {
+                for line in Self::reward_breakdown_lines(breakdown) {
+                    println!("{}", line);
+                }
+            }
+            None => {
+                println!("{}", "No itemised breakdown was saved for this quest (it predates breakdowns).".dimmed());
+                println!();
+                println!("  XP:    {} (focus +{}, habit +{})", record.xp, record.focus_xp, record.habit_xp);
+                println!("  Gold:  {} (habit +{}, loot +{})", record.total_gold(), record.habit_gold, record.bonus_gold);
+                if let Some(multiplier) = record.trivial_multiplier {
+                    println!("  Trivial for the level: ×{:.2}", multiplier);
+                }
+                if let Some(multiplier) = record.instant_multiplier {
+                    println!("  Completed right after it was added: ×{:.2}", multiplier);
+                }
+                if let Some(ref loot) = record.loot {
+                    println!("  Loot:  {}", loot);
+                }
+            }
+        }
+        println!();
+        println!("{}", "Class and streaks don't change quest rewards.".dimmed());
+        println!();
+    }
+
+    pub fn print_rest(character: &Character, config: &RestConfig, today: chrono::NaiveDate) {
+        println!();
+        match character.resting_on(today) {
+            Some(period) => println!("🏖️  Resting since {}{}", period.start,
+                period.end.as_ref().map(|end| format!(" through {}", end)).unwrap_or_default()),
+            None => println!("⚔️  Not resting"),
+        }
+
+        if config.weekly.is_empty() {
+            println!("Weekly rest days: none");
+        } else {
+            let days: Vec<_> = config.weekly.iter().map(|d| d.to_string()).collect();
+            println!("Weekly rest days: {}", days.join(", "));
+        }
+
+        if !character.rest_periods.is_empty() {
+            println!();
+            println!("{}", "REST PERIODS".bold());
+            for period in character.rest_periods.iter().rev().take(10) {
+                println!("  {} → {}", period.start, period.end.as_deref().unwrap_or("(ongoing)"));
+            }
+        }
+        println!();
+    }
+
+    pub fn print_doctor(findings: &[Finding], fix: bool) {
+        println!();
+        println!("{}", "╔════════════════════════════════════════╗".cyan());
+        println!("{}", "║        🩺  TASKQUEST DOCTOR  🩺        ║".cyan().bold());
+        println!("{}", "╚════════════════════════════════════════╝".cyan());
+        println!();
+
+        for finding in findings {
+            let (icon, message) = match (finding.severity, finding.fixed) {
+                (Severity::Ok, _) | (_, true) => ("✓".green(), finding.message.normal()),
+                (Severity::Warning, _) => ("⚠".yellow(), finding.message.yellow()),
+                (Severity::Error, _) => ("✗".red(), finding.message.red()),
+            };
+            println!("{} {:<13} {}", icon, finding.check.bold(), message);
+
+            if let Some(ref action) = finding.fix {
+                if finding.fixed {
+                    println!("  {:<13} {}", "", format!("fixed: {}", action).green());
+                } else if finding.is_outstanding() {
+                    println!("  {:<13} {}", "", format!("--fix will {}", action).dimmed());
+                }
+            }
+        }
+
+        let outstanding = findings.iter().filter(|f| f.is_outstanding()).count();
+        let fixable = findings.iter().filter(|f| f.is_outstanding() && f.fix.is_some()).count();
+        let fixed = findings.iter().filter(|f| f.fixed).count();
+
+        println!();
+        if fixed > 0 {
+            println!("{}", format!("Fixed {} problem(s).", fixed).green().bold());
+        }
+        if outstanding == 0 {
+            println!("{}", "Everything looks healthy.".green().bold());
+        } else if fixable > 0 && !fix {
+            println!("{} problem(s) found. Run 'taskquest doctor --fix' to repair {} of them.", outstanding, fixable);
+        } else {
+            println!("{} problem(s) need manual attention.", outstanding);
+        }
+        println!();
+    }
+
+    pub fn print_purchase_history(purchases: &[PurchaseRecord], limit: usize) {
+        println!();
+        println!("{}", "╔════════════════════════════════════════════════════════════╗".cyan());
+        println!("{}", "║                  🧾  PURCHASE HISTORY  🧾                  ║".cyan().bold());
+        println!("{}", "╚════════════════════════════════════════════════════════════╝".cyan());
+        println!();
+
+        if purchases.is_empty() {
+            println!("No purchases yet.");
+            println!();
+            return;
+        }
+
+        println!("{}", format!("{:<17} {:<24} {:<10} {:>6} {:>8}",
+            "DATE", "REWARD", "TIER", "COST", "BALANCE").bold());
+        println!("{}", "─".repeat(69));
+        for purchase in purchases.iter().rev().take(limit) {
+            let date = purchase.purchased_at()
+                .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| purchase.purchased_at.clone());
+            println!("{:<17} {:<24} {:<10} {} {:>8}",
+                date,
+                purchase.reward_name,
+                purchase.tier.name(),
+                format!("{:>6}", purchase.cost).yellow(),
+                purchase.gold_after
+            );
+        }
+        println!("{}", "─".repeat(69));
+
+        let total: u32 = purchases.iter().map(|p| p.cost).sum();
+        println!("{} purchase{}, {} gold spent in total",
+            purchases.len(),
+            if purchases.len() == 1 { "" } else { "s" },
+            total.to_string().yellow().bold()
+        );
+        println!();
+    }
 }
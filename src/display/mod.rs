@@ -1,5 +1,6 @@
 pub mod cli;
 pub mod formatter;
+pub mod charts;
 
 pub use cli::CLI;
 pub use formatter::Formatter;
@@ -0,0 +1,555 @@
+//! Integrity checks for `taskquest doctor`.
+//!
+//! Data checks run against in-memory copies so they can be tested without a
+//! data directory; `Doctor::run` loads the files, runs every check and writes
+//! back whatever `--fix` repaired.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+use crate::achievements::{Achievement, AchievementTracker};
+use crate::character::{Character, LevelSystem, StatType, Stats};
+use crate::history::CompletionHistory;
+use crate::shop::RewardStore;
+use crate::storage::{migrations, safe_write, Versioned};
+use crate::taskwarrior::UDAManager;
+
+/// Hooks TaskQuest expects in the Taskwarrior hooks directory
+pub const HOOK_NAMES: [&str; 3] = ["on-add-taskquest", "on-modify-taskquest", "on-exit-taskquest"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Ok,
+    Warning,
+    Error,
+}
+
+/// Result of a single check
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub check: &'static str,
+    pub severity: Severity,
+    pub message: String,
+    /// What `--fix` does about it (None if it has to be fixed by hand)
+    pub fix: Option<String>,
+    pub fixed: bool,
+}
+
+impl Finding {
+    fn ok(check: &'static str, message: impl Into<String>) -> Self {
+        Self { check, severity: Severity::Ok, message: message.into(), fix: None, fixed: false }
+    }
+
+    fn problem(check: &'static str, severity: Severity, message: impl Into<String>, fix: Option<String>) -> Self {
+        Self { check, severity, message: message.into(), fix, fixed: false }
+    }
+
+    /// A problem that has not been (and cannot be) repaired
+    pub fn is_outstanding(&self) -> bool {
+        self.severity != Severity::Ok && !self.fixed
+    }
+}
+
+/// Push a repairable finding, applying `repair` when fixing
+fn repairable(
+    findings: &mut Vec<Finding>,
+    fix: bool,
+    mut finding: Finding,
+    repair: impl FnOnce() -> Result<()>,
+) {
+    if fix {
+        match repair() {
+            Ok(()) => finding.fixed = true,
+            Err(e) => finding.message = format!("{} (fix failed: {:#})", finding.message, e),
+        }
+    }
+    findings.push(finding);
+}
+
+pub struct Doctor;
+
+impl Doctor {
+    /// Level matches XP and every stat is in range
+    pub fn check_character(character: &mut Character, fix: bool) -> Vec<Finding> {
+        let mut findings = Vec::new();
+
+        let expected = LevelSystem::level_from_xp(character.total_xp);
+        if character.level == expected {
+            findings.push(Finding::ok("level", format!("Level {} matches {} XP", expected, character.total_xp)));
+        } else {
+            let finding = Finding::problem(
+                "level",
+                Severity::Error,
+                format!("Level is {} but {} XP is level {}", character.level, character.total_xp, expected),
+                Some(format!("set level to {}", expected)),
+            );
+            repairable(&mut findings, fix, finding, || {
+                character.level = expected;
+                Ok(())
+            });
+        }
+
+        let mut all_in_range = true;
+        for &stat in StatType::all() {
+            let value = character.stats.get_stat_raw(stat);
+            if Stats::is_in_range(value) {
+                continue;
+            }
+            all_in_range = false;
+
+            let clamped = Stats::clamp_value(value);
+            let finding = Finding::problem(
+                "stats",
+                Severity::Error,
+                format!("{:?} is {} (valid range is 10-99)", stat, value),
+                Some(format!("set {:?} to {}", stat, clamped)),
+            );
+            repairable(&mut findings, fix, finding, || {
+                character.stats.set_stat_raw(stat, clamped);
+                Ok(())
+            });
+        }
+        if all_in_range {
+            findings.push(Finding::ok("stats", "All stats are within 10-99"));
+        }
+
+        findings
+    }
+
+    /// Every unlocked achievement exists
+    pub fn check_achievements(tracker: &mut AchievementTracker, fix: bool) -> Vec<Finding> {
+        let mut findings = Vec::new();
+
+        let mut unknown: Vec<String> = tracker.unlocked.iter()
+            .filter(|id| Achievement::get_by_id(id).is_none())
+            .cloned()
+            .collect();
+        unknown.sort();
+
+        if unknown.is_empty() {
+            findings.push(Finding::ok("achievements", format!("{} unlocked achievements are valid", tracker.unlocked.len())));
+        } else {
+            let finding = Finding::problem(
+                "achievements",
+                Severity::Warning,
+                format!("Unknown achievement ids: {}", unknown.join(", ")),
+                Some("remove them from the unlocked list".to_string()),
+            );
+            repairable(&mut findings, fix, finding, || {
+                tracker.unlocked.retain(|id| !unknown.contains(id));
+                Ok(())
+            });
+        }
+
+        findings
+    }
+
+    /// `next_id` is past every reward and purchase timestamps parse
+    pub fn check_shop(shop: &mut RewardStore, fix: bool) -> Vec<Finding> {
+        let mut findings = Vec::new();
+
+        let max_id = shop.rewards.iter().map(|r| r.id).max().unwrap_or(0);
+        if shop.next_id > max_id {
+            findings.push(Finding::ok("shop ids", format!("Next reward id {} is unused", shop.next_id)));
+        } else {
+            let finding = Finding::problem(
+                "shop ids",
+                Severity::Error,
+                format!("Next reward id {} is not greater than existing id {}", shop.next_id, max_id),
+                Some(format!("set next id to {}", max_id + 1)),
+            );
+            repairable(&mut findings, fix, finding, || {
+                shop.next_id = max_id + 1;
+                Ok(())
+            });
+        }
+
+        let mut all_valid = true;
+        for reward in &mut shop.rewards {
+            let Some(ref last) = reward.last_purchased else { continue };
+            if chrono::DateTime::parse_from_rfc3339(last).is_ok() {
+                continue;
+            }
+            all_valid = false;
+
+            let finding = Finding::problem(
+                "cooldowns",
+                Severity::Error,
+                format!("'{}' has an unreadable purchase time: {}", reward.name, last),
+                Some("clear it (ends the cooldown)".to_string()),
+            );
+            repairable(&mut findings, fix, finding, || {
+                reward.last_purchased = None;
+                Ok(())
+            });
+        }
+        if all_valid {
+            findings.push(Finding::ok("cooldowns", "All purchase times are valid"));
+        }
+
+        findings
+    }
+
+    /// The main copy of a data file loads; a broken one is restored from `.bak`
+    pub fn check_file<T: Versioned>(path: &Path, fix: bool) -> Finding {
+        const CHECK: &str = "files";
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("?").to_string();
+
+        if !path.exists() {
+            return Finding::ok(CHECK, format!("{} not created yet", name));
+        }
+
+        let error = match migrations::load_versioned::<T>(path) {
+            Ok(_) => return Finding::ok(CHECK, format!("{} loads", name)),
+            Err(e) => e,
+        };
+
+        let bak_path = path.with_extension("bak");
+        if migrations::load_versioned::<T>(&bak_path).is_err() {
+            return Finding::problem(
+                CHECK,
+                Severity::Error,
+                format!("{} is unreadable and has no usable backup: {:#}", name, error),
+                None,
+            );
+        }
+
+        let mut finding = Finding::problem(
+            CHECK,
+            Severity::Error,
+            format!("{} is unreadable: {:#}", name, error),
+            Some(format!("restore it from {}", bak_path.display())),
+        );
+        if fix {
+            match std::fs::copy(&bak_path, path) {
+                Ok(_) => finding.fixed = true,
+                Err(e) => finding.message = format!("{} (fix failed: {})", finding.message, e),
+            }
+        }
+        finding
+    }
+
+    /// Hooks exist, are executable and point at this binary
+    #[cfg(unix)]
+    pub fn check_hooks(hooks_dir: &Path, fix: bool) -> Vec<Finding> {
+        let binary = std::env::current_exe().and_then(std::fs::canonicalize).ok();
+        let mut findings = Vec::new();
+
+        for hook in HOOK_NAMES {
+            let path = hooks_dir.join(hook);
+            let target = std::fs::canonicalize(&path).ok();
+            let problem = if !path.exists() {
+                Some((Severity::Error, "is not installed".to_string()))
+            } else if !is_executable(&path) {
+                Some((Severity::Error, "is not executable".to_string()))
+            } else if binary.is_some() && target != binary {
+                let target = std::fs::read_link(&path).unwrap_or_else(|_| path.clone());
+                Some((Severity::Warning, format!("points at {}, not this binary", target.display())))
+            } else {
+                None
+            };
+
+            match problem {
+                None => findings.push(Finding::ok("hooks", format!("{} installed", hook))),
+                Some((severity, problem)) => {
+                    let finding = Finding::problem(
+                        "hooks",
+                        severity,
+                        format!("{} {}", hook, problem),
+                        Some(format!("link {} to the taskquest binary", path.display())),
+                    );
+                    repairable(&mut findings, fix, finding, || install_hook(&path));
+                }
+            }
+        }
+
+        findings
+    }
+
+    /// Hooks are symlinks to the binary, which only unix supports
+    #[cfg(not(unix))]
+    pub fn check_hooks(_hooks_dir: &Path, _fix: bool) -> Vec<Finding> {
+        vec![Finding::problem("hooks", Severity::Warning, "hook checks are unsupported on this platform", None)]
+    }
+
+    /// TaskQuest UDAs are present in ~/.taskrc
+    pub fn check_udas(fix: bool) -> Finding {
+        let missing = match UDAManager::missing() {
+            Ok(missing) => missing,
+            Err(e) => return Finding::problem("udas", Severity::Warning, format!("Could not read ~/.taskrc: {:#}", e), None),
+        };
+        if missing.is_empty() {
+            return Finding::ok("udas", "Taskwarrior UDAs configured");
+        }
+
+        let mut finding = Finding::problem(
+            "udas",
+            Severity::Error,
+            format!("Taskwarrior UDAs missing: {}", missing.join(", ")),
+            Some("add them to ~/.taskrc".to_string()),
+        );
+        if fix {
+            match UDAManager::configure() {
+                Ok(()) => finding.fixed = true,
+                Err(e) => finding.message = format!("{} (fix failed: {:#})", finding.message, e),
+            }
+        }
+        finding
+    }
+
+    /// Git repository opens, has commits and no unfinished merge
+    pub fn check_git(data_dir: &Path) -> Vec<Finding> {
+        use git2::{Repository, RepositoryState};
+
+        if !data_dir.join(".git").exists() {
+            return vec![Finding::ok("git", "Git sync not set up (optional)")];
+        }
+
+        let repo = match Repository::open(data_dir) {
+            Ok(repo) => repo,
+            Err(e) => {
+                return vec![Finding::problem(
+                    "git",
+                    Severity::Error,
+                    format!("Repository cannot be opened: {}", e.message()),
+                    None,
+                )];
+            }
+        };
+
+        let mut findings = Vec::new();
+        let git_hint = format!("git -C {}", data_dir.display());
+
+        if repo.state() != RepositoryState::Clean {
+            findings.push(Finding::problem(
+                "git",
+                Severity::Error,
+                format!("An operation is in progress ({:?}); finish or abort it with '{} merge --abort'", repo.state(), git_hint),
+                None,
+            ));
+        }
+        if repo.index().map(|index| index.has_conflicts()).unwrap_or(false) {
+            findings.push(Finding::problem(
+                "git",
+                Severity::Error,
+                format!("Unresolved merge conflicts; see '{} status'", git_hint),
+                None,
+            ));
+        }
+        if repo.head().is_err() {
+            findings.push(Finding::problem(
+                "git",
+                Severity::Warning,
+                "Repository has no commits; run 'taskquest sync push'",
+                None,
+            ));
+        }
+        if repo.find_remote("origin").is_err() {
+            findings.push(Finding::problem(
+                "git",
+                Severity::Warning,
+                format!("No 'origin' remote; add one with '{} remote add origin <url>'", git_hint),
+                None,
+            ));
+        }
+
+        if findings.is_empty() {
+            findings.push(Finding::ok("git", "Repository healthy"));
+        }
+        findings
+    }
+
+    /// Run every check on the data directory, saving repaired files when fixing
+    pub fn run(data_dir: &Path, fix: bool) -> Result<Vec<Finding>> {
+        let character_path = data_dir.join("character.json");
+        let achievements_path = data_dir.join("achievements.json");
+        let shop_path = data_dir.join("shop.json");
+        let history_path = data_dir.join("history.json");
+
+        let mut findings = vec![
+            Self::check_file::<Character>(&character_path, fix),
+            Self::check_file::<AchievementTracker>(&achievements_path, fix),
+            Self::check_file::<RewardStore>(&shop_path, fix),
+            Self::check_file::<CompletionHistory>(&history_path, fix),
+        ];
+
+        if character_path.exists() {
+            if let Ok(mut character) = crate::storage::load_with_backup::<Character>(&character_path) {
+                let character_findings = Self::check_character(&mut character, fix);
+                if character_findings.iter().any(|f| f.fixed) {
+                    safe_write(&character_path, &character)?;
+                }
+                findings.extend(character_findings);
+            }
+        } else {
+            findings.push(Finding::problem(
+                "character",
+                Severity::Error,
+                "No character yet; run 'taskquest init'",
+                None,
+            ));
+        }
+
+        if let Ok(mut tracker) = AchievementTracker::load(&achievements_path) {
+            let tracker_findings = Self::check_achievements(&mut tracker, fix);
+            if tracker_findings.iter().any(|f| f.fixed) {
+                safe_write(&achievements_path, &tracker)?;
+            }
+            findings.extend(tracker_findings);
+        }
+
+        if let Ok(mut shop) = RewardStore::load(&shop_path) {
+            let shop_findings = Self::check_shop(&mut shop, fix);
+            if shop_findings.iter().any(|f| f.fixed) {
+                safe_write(&shop_path, &shop)?;
+            }
+            findings.extend(shop_findings);
+        }
+
+        findings.extend(Self::check_hooks(&hooks_dir()?, fix));
+        findings.push(Self::check_udas(fix));
+        findings.extend(Self::check_git(data_dir));
+
+        Ok(findings)
+    }
+}
+
+/// Taskwarrior hooks directory (`$TASKDATA/hooks`, default `~/.task/hooks`)
+pub fn hooks_dir() -> Result<PathBuf> {
+    if let Ok(task_data) = std::env::var("TASKDATA") {
+        return Ok(PathBuf::from(task_data).join("hooks"));
+    }
+
+    let home = std::env::var("HOME").context("HOME environment variable not set")?;
+    Ok(PathBuf::from(home).join(".task").join("hooks"))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::metadata(path)
+        .map(|m| m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+/// Symlink a hook to the running binary; it picks the hook from its file name
+#[cfg(unix)]
+fn install_hook(path: &Path) -> Result<()> {
+    let binary = std::env::current_exe()
+        .context("Failed to locate the taskquest binary")?;
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .context("Failed to create hooks directory")?;
+    }
+    if path.symlink_metadata().is_ok() {
+        std::fs::remove_file(path)
+            .context("Failed to remove old hook")?;
+    }
+
+    std::os::unix::fs::symlink(&binary, path)
+        .context("Failed to link hook")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character::Class;
+    use crate::progression::RewardTier;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_character_repairs() {
+        let mut character = Character::new("Test".to_string(), Class::Ranger);
+        character.total_xp = 500;
+        character.stats.wisdom = 150.0;
+
+        let findings = Doctor::check_character(&mut character, false);
+        assert_eq!(findings.iter().filter(|f| f.is_outstanding()).count(), 2);
+        assert_eq!(character.level, 1);
+
+        let findings = Doctor::check_character(&mut character, true);
+        assert!(findings.iter().all(|f| !f.is_outstanding()));
+        assert_eq!(character.level, LevelSystem::level_from_xp(500));
+        assert_eq!(character.stats.wisdom, 99.0);
+    }
+
+    #[test]
+    fn test_unknown_achievements_removed() {
+        let mut tracker = AchievementTracker::new();
+        tracker.unlocked.insert("first_steps".to_string());
+        tracker.unlocked.insert("made_up".to_string());
+
+        let findings = Doctor::check_achievements(&mut tracker, true);
+        assert!(findings[0].fixed);
+        assert!(tracker.unlocked.contains("first_steps"));
+        assert!(!tracker.unlocked.contains("made_up"));
+    }
+
+    #[test]
+    fn test_shop_repairs() {
+        let mut shop = RewardStore::new();
+        let id = shop.add_reward("Nap".to_string(), 10, "Sleep".to_string(), RewardTier::Normal, 4);
+        shop.next_id = id;
+        shop.rewards[0].last_purchased = Some("yesterday".to_string());
+
+        let findings = Doctor::check_shop(&mut shop, true);
+        assert_eq!(findings.iter().filter(|f| f.fixed).count(), 2);
+        assert_eq!(shop.next_id, id + 1);
+        assert!(shop.rewards[0].last_purchased.is_none());
+    }
+
+    #[test]
+    fn test_corrupt_file_restored_from_backup() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("shop.json");
+        safe_write(&path, &RewardStore::new()).unwrap();
+        safe_write(&path, &RewardStore::new()).unwrap();
+        std::fs::write(&path, "{ not json").unwrap();
+
+        let finding = Doctor::check_file::<RewardStore>(&path, false);
+        assert!(finding.is_outstanding() && finding.fix.is_some());
+
+        let finding = Doctor::check_file::<RewardStore>(&path, true);
+        assert!(finding.fixed);
+        assert!(migrations::load_versioned::<RewardStore>(&path).is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_missing_hooks_installed() {
+        let dir = tempdir().unwrap();
+        let hooks = dir.path().join("hooks");
+
+        let findings = Doctor::check_hooks(&hooks, true);
+        assert!(findings.iter().all(|f| f.fixed));
+        assert!(Doctor::check_hooks(&hooks, false).iter().all(|f| f.severity == Severity::Ok));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hooks_pointing_elsewhere_relinked() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let hooks = dir.path().join("hooks");
+        Doctor::check_hooks(&hooks, true);
+
+        // An old install somewhere else
+        let other = dir.path().join("old-taskquest");
+        std::fs::write(&other, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&other, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let hook = hooks.join(HOOK_NAMES[0]);
+        std::fs::remove_file(&hook).unwrap();
+        std::os::unix::fs::symlink(&other, &hook).unwrap();
+
+        let findings = Doctor::check_hooks(&hooks, false);
+        assert_eq!(findings.iter().filter(|f| f.is_outstanding()).count(), 1);
+        assert!(findings[0].message.contains("not this binary"));
+
+        assert!(Doctor::check_hooks(&hooks, true)[0].fixed);
+        assert!(Doctor::check_hooks(&hooks, false).iter().all(|f| f.severity == Severity::Ok));
+    }
+}
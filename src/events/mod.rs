@@ -0,0 +1,325 @@
+//! Outbound event handlers.
+//!
+//! Handlers are shell commands from the `events` section of `config.json`.
+//! Each matching event is written to the command's stdin as one line of JSON.
+//! Commands run under a detached `taskquest event-handler` supervisor that
+//! enforces the timeout, so the Taskwarrior hook never waits on them.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::achievements::Achievement;
+use crate::progression::LootDrop;
+
+/// Kinds of event a handler can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    QuestCompleted,
+    LevelUp,
+    AchievementUnlocked,
+    LootDropped,
+    RewardPurchased,
+    StreakBroken,
+}
+
+/// Something that happened, as sent to handlers
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    QuestCompleted {
+        uuid: String,
+        description: String,
+        project: Option<String>,
+        challenge: u8,
+        xp: u32,
+        gold: u32,
+    },
+    LevelUp {
+        from: u32,
+        to: u32,
+    },
+    AchievementUnlocked {
+        id: String,
+        title: String,
+        tier: String,
+    },
+    LootDropped {
+        loot: LootDrop,
+    },
+    RewardPurchased {
+        reward_id: u32,
+        reward_name: String,
+        cost: u32,
+        gold_after: u32,
+    },
+    StreakBroken {
+        previous_streak: u32,
+    },
+}
+
+impl Event {
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Event::QuestCompleted { .. } => EventKind::QuestCompleted,
+            Event::LevelUp { .. } => EventKind::LevelUp,
+            Event::AchievementUnlocked { .. } => EventKind::AchievementUnlocked,
+            Event::LootDropped { .. } => EventKind::LootDropped,
+            Event::RewardPurchased { .. } => EventKind::RewardPurchased,
+            Event::StreakBroken { .. } => EventKind::StreakBroken,
+        }
+    }
+
+    pub fn achievement(achievement: &Achievement) -> Self {
+        Event::AchievementUnlocked {
+            id: achievement.id.to_string(),
+            title: achievement.title.to_string(),
+            tier: achievement.tier.name().to_string(),
+        }
+    }
+
+    /// One-line human readable summary, handy for notifications
+    pub fn message(&self) -> String {
+        match self {
+            Event::QuestCompleted { description, xp, gold, .. } =>
+                format!("Quest complete: {} (+{} XP, +{} gold)", description, xp, gold),
+            Event::LevelUp { to, .. } => format!("Level up! Now level {}", to),
+            Event::AchievementUnlocked { title, tier, .. } =>
+                format!("Achievement unlocked: {} [{}]", title, tier),
+            Event::LootDropped { loot: LootDrop::Gold(amount) } => format!("Loot drop: {} bonus gold", amount),
+            Event::LootDropped { loot: LootDrop::Reward { name, .. } } => format!("Loot drop: {}", name),
+            Event::RewardPurchased { reward_name, cost, .. } =>
+                format!("Bought {} for {} gold", reward_name, cost),
+            Event::StreakBroken { previous_streak } =>
+                format!("Streak broken after {} day(s)", previous_streak),
+        }
+    }
+}
+
+/// The JSON document a handler receives on stdin
+#[derive(Debug, Serialize)]
+pub struct EventPayload<'a> {
+    #[serde(flatten)]
+    pub event: &'a Event,
+    pub message: String,
+    pub character: &'a str,
+    pub timestamp: String, // ISO 8601 datetime
+}
+
+/// A command run for some (or all) events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventHandler {
+    /// Shell command; the event arrives as JSON on stdin
+    pub command: String,
+    /// Events this handler wants (all of them when empty)
+    #[serde(default)]
+    pub events: Vec<EventKind>,
+    /// Overrides the section-wide timeout
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+impl EventHandler {
+    pub fn handles(&self, kind: EventKind) -> bool {
+        self.events.is_empty() || self.events.contains(&kind)
+    }
+}
+
+/// `events` section of config.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventsConfig {
+    /// Seconds a handler may run before it is killed
+    pub timeout_secs: u64,
+    pub handlers: Vec<EventHandler>,
+}
+
+impl Default for EventsConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: 5,
+            handlers: Vec::new(),
+        }
+    }
+}
+
+/// Outcome of running one handler under the supervisor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandlerOutcome {
+    Exited(Option<i32>),
+    TimedOut,
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    use std::os::unix::process::CommandExt;
+
+    // Own process group, so a timeout kills everything the command started
+    let mut cmd = Command::new("sh");
+    cmd.args(["-c", command]).process_group(0);
+    cmd
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.args(["/C", command]);
+    cmd
+}
+
+fn kill(child: &mut std::process::Child) {
+    #[cfg(unix)]
+    // SAFETY: plain syscall; the group id is the child's pid (see `shell`)
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+pub struct Events;
+
+impl Events {
+    /// Hand every event to the handlers that want it, without waiting for them.
+    /// Failures are reported as warnings; they never fail the caller.
+    pub fn emit(config: &EventsConfig, character: &str, events: &[Event]) {
+        if config.handlers.is_empty() || events.is_empty() {
+            return;
+        }
+
+        // Resolve the hook symlink, which would otherwise start as a hook again
+        let exe = match std::env::current_exe().and_then(|exe| exe.canonicalize()) {
+            Ok(exe) => exe,
+            Err(e) => {
+                eprintln!("Warning: Failed to run event handlers: {}", e);
+                return;
+            }
+        };
+        let timestamp = chrono::Utc::now().to_rfc3339();
+
+        for event in events {
+            let payload = EventPayload {
+                event,
+                message: event.message(),
+                character,
+                timestamp: timestamp.clone(),
+            };
+            let json = match serde_json::to_string(&payload) {
+                Ok(json) => json,
+                Err(e) => {
+                    eprintln!("Warning: Failed to serialize event: {}", e);
+                    continue;
+                }
+            };
+
+            for handler in config.handlers.iter().filter(|h| h.handles(event.kind())) {
+                let timeout = handler.timeout_secs.unwrap_or(config.timeout_secs);
+                if let Err(e) = Self::spawn_supervisor(&exe, &handler.command, timeout, &json) {
+                    eprintln!("Warning: Event handler '{}' failed to start: {}", handler.command, e);
+                }
+            }
+        }
+    }
+
+    /// Start `taskquest event-handler` detached from our stdout/stderr so
+    /// Taskwarrior doesn't wait for it, and pass it the payload
+    fn spawn_supervisor(exe: &std::path::Path, command: &str, timeout_secs: u64, json: &str) -> Result<()> {
+        let mut child = Command::new(exe)
+            .args(["event-handler", "--timeout", &timeout_secs.to_string(), "--", command])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .context("Failed to start supervisor")?;
+
+        let mut stdin = child.stdin.take().context("Supervisor has no stdin")?;
+        writeln!(stdin, "{}", json).context("Failed to send event")?;
+        Ok(())
+    }
+
+    /// Run a handler command with the payload on stdin, killing it at the timeout
+    pub fn run_handler(command: &str, payload: &str, timeout: Duration) -> Result<HandlerOutcome> {
+        let mut child = shell(command)
+            .stdin(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to run '{}'", command))?;
+
+        // A handler that ignores stdin may close it early; that's fine
+        if let Some(mut stdin) = child.stdin.take() {
+            let _ = writeln!(stdin, "{}", payload.trim_end());
+        }
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(status) = child.try_wait()? {
+                return Ok(HandlerOutcome::Exited(status.code()));
+            }
+            if Instant::now() >= deadline {
+                kill(&mut child);
+                return Ok(HandlerOutcome::TimedOut);
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_json() {
+        let event = Event::LevelUp { from: 4, to: 5 };
+        let payload = EventPayload {
+            event: &event,
+            message: event.message(),
+            character: "Aria",
+            timestamp: "2026-01-01T00:00:00+00:00".to_string(),
+        };
+        let value = serde_json::to_value(&payload).unwrap();
+
+        assert_eq!(value["event"], "level_up");
+        assert_eq!(value["to"], 5);
+        assert_eq!(value["character"], "Aria");
+        assert_eq!(value["message"], "Level up! Now level 5");
+    }
+
+    #[test]
+    fn test_handler_filter_from_config() {
+        let config: EventsConfig = serde_json::from_str(r#"{
+            "handlers": [
+                { "command": "notify-send TaskQuest", "events": ["level_up", "streak_broken"] },
+                { "command": "logger", "timeout_secs": 1 }
+            ]
+        }"#).unwrap();
+
+        assert_eq!(config.timeout_secs, 5);
+        assert!(config.handlers[0].handles(EventKind::LevelUp));
+        assert!(!config.handlers[0].handles(EventKind::RewardPurchased));
+        assert!(config.handlers[1].handles(EventKind::RewardPurchased));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_handler_receives_payload() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("event.json");
+        let command = format!("cat > '{}'", out.display());
+
+        let outcome = Events::run_handler(&command, r#"{"event":"level_up"}"#, Duration::from_secs(5)).unwrap();
+        assert_eq!(outcome, HandlerOutcome::Exited(Some(0)));
+        assert_eq!(std::fs::read_to_string(out).unwrap().trim(), r#"{"event":"level_up"}"#);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_handler_times_out() {
+        let start = Instant::now();
+        let outcome = Events::run_handler("sleep 10; sleep 10", "{}", Duration::from_millis(200)).unwrap();
+        assert_eq!(outcome, HandlerOutcome::TimedOut);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
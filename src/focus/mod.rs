@@ -0,0 +1,67 @@
+//! Focus (pomodoro) sessions run with `taskquest focus`.
+//!
+//! A session on a task earns a little XP and trains the task's stats for the
+//! minutes actually spent, so a session stopped early still counts in part.
+
+pub mod timer;
+
+use serde::{Deserialize, Serialize};
+
+/// Minutes in a standard pomodoro, the unit `stat_share` is measured in
+const POMODORO_MINUTES: f64 = 25.0;
+
+/// `focus` section of config.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FocusConfig {
+    /// Session length when `--minutes` isn't given
+    pub default_minutes: u32,
+    /// XP per focused minute
+    pub xp_per_minute: f64,
+    /// Stat training per 25 focused minutes, as a share of what completing
+    /// the task trains
+    pub stat_share: f64,
+    /// Sessions stopped before this many minutes earn nothing
+    pub min_minutes: u32,
+    /// Shortest planned length that counts toward the session achievements
+    pub min_full_session: u32,
+}
+
+impl Default for FocusConfig {
+    fn default() -> Self {
+        Self {
+            default_minutes: 25,
+            xp_per_minute: 0.4,
+            stat_share: 0.25,
+            min_minutes: 5,
+            min_full_session: 25,
+        }
+    }
+}
+
+impl FocusConfig {
+    pub fn session_xp(&self, minutes: u32) -> u32 {
+        (minutes as f64 * self.xp_per_minute).round() as u32
+    }
+
+    /// Stat1 gain for a session (stat2 gets half, as for quests)
+    pub fn stat_gain(&self, challenge: u8, minutes: u32) -> f64 {
+        challenge as f64 / 20.0 * self.stat_share * minutes as f64 / POMODORO_MINUTES
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_sessions_earn_in_proportion() {
+        let config = FocusConfig::default();
+        assert_eq!(config.session_xp(25), 10);
+        assert_eq!(config.session_xp(10), 4);
+
+        // A full pomodoro on a challenge 8 task trains a quarter of completing it
+        assert!((config.stat_gain(8, 25) - 0.1).abs() < 1e-9);
+        assert!((config.stat_gain(8, 10) - 0.04).abs() < 1e-9);
+    }
+}
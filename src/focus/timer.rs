@@ -0,0 +1,60 @@
+use std::io::BufRead;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Set by Ctrl-C or Enter to end the session early
+static STOP: AtomicBool = AtomicBool::new(false);
+
+#[cfg(unix)]
+extern "C" fn on_interrupt(_: libc::c_int) {
+    STOP.store(true, Ordering::SeqCst);
+}
+
+/// Catch Ctrl-C so an interrupted session can still be credited
+fn catch_interrupt() {
+    #[cfg(unix)]
+    // SAFETY: the handler only stores to an atomic, which is signal safe
+    unsafe {
+        libc::signal(libc::SIGINT, on_interrupt as *const () as libc::sighandler_t);
+    }
+}
+
+fn restore_interrupt() {
+    #[cfg(unix)]
+    // SAFETY: restores the default disposition
+    unsafe {
+        libc::signal(libc::SIGINT, libc::SIG_DFL);
+    }
+}
+
+/// Run a session of `length`, calling `tick` with the elapsed time about once
+/// a second. Ends early on Ctrl-C or Enter; returns the time actually spent.
+pub fn run(length: Duration, mut tick: impl FnMut(Duration)) -> Duration {
+    STOP.store(false, Ordering::SeqCst);
+    catch_interrupt();
+
+    // Left blocked on stdin once the session ends; the process exits soon after
+    std::thread::spawn(|| {
+        let mut line = String::new();
+        if std::io::stdin().lock().read_line(&mut line).is_ok_and(|n| n > 0) {
+            STOP.store(true, Ordering::SeqCst);
+        }
+    });
+
+    let start = Instant::now();
+    let mut next_tick = Duration::ZERO;
+    let elapsed = loop {
+        let elapsed = start.elapsed().min(length);
+        if elapsed >= next_tick {
+            tick(elapsed);
+            next_tick += Duration::from_secs(1);
+        }
+        if elapsed >= length || STOP.load(Ordering::SeqCst) {
+            break elapsed;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    };
+
+    restore_interrupt();
+    elapsed
+}
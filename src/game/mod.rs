@@ -0,0 +1,826 @@
+//! High-level API: a loaded profile and the things that happen to it.
+//!
+//! [`Profile`] bundles the character, achievements, shop, history and config
+//! of one data directory. Its methods apply game rules in memory; nothing
+//! touches disk until [`Profile::save`].
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::achievements::definitions::ACHIEVEMENTS;
+use crate::achievements::{Achievement, AchievementTracker, MissedDays, Title};
+use crate::character::{Character, Class, PrestigeSystem, RestDays};
+use crate::config::Config;
+use crate::events::Event;
+use crate::history::{calibration, habits, CompletionHistory, CompletionRecord, FocusSession};
+use crate::progression::{Breakdown, GoldCalculator, LootDrop, LootSystem, RewardBreakdown, StatBonuses, TaskTiming, XPCalculator};
+use crate::shop::{PriceContext, PricingMode, RewardStore};
+use crate::storage::{get_data_dir, load_with_backup, safe_write};
+use crate::taskwarrior::{challenge, Suggestion, TaskData};
+use crate::timewarrior::{self, DailyFocus, Interval};
+
+/// Everything stored for one profile
+#[derive(Clone)]
+pub struct Profile {
+    data_dir: PathBuf,
+    pub config: Config,
+    pub character: Character,
+    pub achievements: AchievementTracker,
+    pub shop: RewardStore,
+    pub history: CompletionHistory,
+    /// Timewarrior intervals, read on first use, and the day they were read
+    /// from (`None` when they cover all time)
+    intervals: Option<(Option<NaiveDate>, Vec<Interval>)>,
+}
+
+/// What completing a task earned
+#[derive(Debug, Clone)]
+pub struct Completion {
+    /// The entry added to the completion history
+    pub record: CompletionRecord,
+    /// Gold set aside for savings goals
+    pub saved_gold: u32,
+    pub level_before: u32,
+    pub achievements: Vec<&'static Achievement>,
+    pub new_titles: Vec<&'static Title>,
+    /// Length of the daily streak this completion broke, if it broke one
+    pub streak_broken: Option<u32>,
+}
+
+impl Completion {
+    pub fn leveled_up(&self) -> bool {
+        self.record.level_after > self.level_before
+    }
+
+    /// The events this completion raised, the quest itself first
+    pub fn events(&self) -> Vec<Event> {
+        let record = &self.record;
+        let mut events = vec![Event::QuestCompleted {
+            uuid: record.uuid.clone(),
+            description: record.description.clone(),
+            project: record.project.clone(),
+            challenge: record.challenge,
+            xp: record.xp,
+            gold: record.total_gold(),
+        }];
+
+        if let Some(ref loot) = record.loot {
+            events.push(Event::LootDropped { loot: loot.clone() });
+        }
+        if self.leveled_up() {
+            events.push(Event::LevelUp { from: self.level_before, to: record.level_after });
+        }
+        events.extend(self.achievements.iter().map(|a| Event::achievement(a)));
+        if let Some(previous_streak) = self.streak_broken {
+            events.push(Event::StreakBroken { previous_streak });
+        }
+        events
+    }
+}
+
+/// What a focus session earned
+#[derive(Debug, Clone)]
+pub struct FocusOutcome {
+    pub session: FocusSession,
+    pub level_before: u32,
+    pub achievements: Vec<&'static Achievement>,
+}
+
+impl FocusOutcome {
+    pub fn leveled_up(&self) -> bool {
+        self.session.level_after > self.level_before
+    }
+
+    pub fn events(&self) -> Vec<Event> {
+        let mut events = Vec::new();
+        if self.leveled_up() {
+            events.push(Event::LevelUp { from: self.level_before, to: self.session.level_after });
+        }
+        events.extend(self.achievements.iter().map(|a| Event::achievement(a)));
+        events
+    }
+}
+
+/// A completed shop purchase
+#[derive(Debug, Clone)]
+pub struct Purchase {
+    pub reward_id: u32,
+    pub reward_name: String,
+    pub cost: u32,
+    pub gold_after: u32,
+    /// A savings goal for this reward was completed by the purchase
+    pub goal_completed: bool,
+    pub achievements: Vec<&'static Achievement>,
+}
+
+impl Purchase {
+    pub fn events(&self) -> Vec<Event> {
+        let mut events = vec![Event::RewardPurchased {
+            reward_id: self.reward_id,
+            reward_name: self.reward_name.clone(),
+            cost: self.cost,
+            gold_after: self.gold_after,
+        }];
+        events.extend(self.achievements.iter().map(|a| Event::achievement(a)));
+        events
+    }
+}
+
+/// A reward deleted from the shop
+#[derive(Debug, Clone)]
+pub struct RemovedReward {
+    pub reward_id: u32,
+    pub reward_name: String,
+    /// Gold that was saved for it and is spendable again
+    pub released_gold: Option<u32>,
+}
+
+/// Snapshot of where a character stands
+#[derive(Debug, Clone, Serialize)]
+pub struct Progress {
+    pub name: String,
+    pub class: Class,
+    pub title: Option<String>,
+    pub level: u32,
+    pub total_xp: u32,
+    pub xp_to_next_level: u32,
+    pub xp_progress_percent: f64,
+    pub gold: u32,
+    pub reserved_gold: u32,
+    pub tasks_completed: u32,
+    pub prestige_rank: u32,
+    pub current_streak: u32,
+    pub longest_streak: u32,
+    pub achievements_unlocked: usize,
+    pub achievements_total: usize,
+}
+
+impl Profile {
+    /// Load the profile stored in `data_dir`. The character must exist;
+    /// missing achievements, shop and history files start out fresh.
+    pub fn open(data_dir: &Path) -> Result<Self> {
+        let character = load_with_backup(&data_dir.join("character.json"))
+            .context("Character not found. Run 'taskquest init' first")?;
+        Self::create(data_dir, character)
+    }
+
+    /// A profile in `data_dir` for a new character. Achievements, shop and
+    /// history already stored there are kept. Nothing is written until
+    /// [`Profile::save`].
+    pub fn create(data_dir: &Path, character: Character) -> Result<Self> {
+        Ok(Self {
+            data_dir: data_dir.to_path_buf(),
+            config: Config::load(data_dir)?,
+            character,
+            achievements: AchievementTracker::load(&data_dir.join("achievements.json"))?,
+            shop: RewardStore::load(&data_dir.join("shop.json"))?,
+            history: CompletionHistory::load(&data_dir.join("history.json"))?,
+            intervals: None,
+        })
+    }
+
+    /// Load the active profile (`--profile`, `TASKQUEST_PROFILE` or the default)
+    pub fn open_active() -> Result<Self> {
+        Self::open(&get_data_dir()?)
+    }
+
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    /// Write the character, achievements, shop and history back to disk
+    pub fn save(&self) -> Result<()> {
+        safe_write(&self.data_dir.join("character.json"), &self.character)?;
+        safe_write(&self.data_dir.join("achievements.json"), &self.achievements)?;
+        safe_write(&self.data_dir.join("shop.json"), &self.shop)?;
+        safe_write(&self.data_dir.join("history.json"), &self.history)?;
+        Ok(())
+    }
+
+    /// Credit a completed task: XP, gold, loot, stat training, achievements
+    /// and a history entry
+    pub fn complete_task(&mut self, task: &TaskData) -> Completion {
+        self.credit_task(task, true)
+    }
+
+    /// Credit a task that was never seen pending, such as one recorded with
+    /// `task log` (added already done) or completed while the hook was off.
+    /// Its entry date says nothing about how fast it was done, so the
+    /// instant completion rule doesn't apply.
+    pub fn complete_logged_task(&mut self, task: &TaskData) -> Completion {
+        self.credit_task(task, false)
+    }
+
+    fn credit_task(&mut self, task: &TaskData, instant_rule: bool) -> Completion {
+        let completion_time = task.get_completion_date().unwrap_or_else(Utc::now);
+        let from = task.get_entry_date().or(task.get_start_date());
+        // Enough Timewarrior history for the task's lifetime and its completion day
+        let since = from.unwrap_or(completion_time).min(completion_time - Duration::days(1)).date_naive();
+        let match_tags = self.config.timewarrior.match_tags;
+        let (focus_minutes, focus_day) = match self.intervals(since) {
+            Some(intervals) => {
+                let minutes = timewarrior::focus_minutes(intervals, task, match_tags, from, completion_time);
+                let day = timewarrior::daily_focus(intervals, completion_time.date_naive(), Utc::now());
+                ((minutes >= 1.0).then_some(minutes), Some(day))
+            }
+            None => (None, None),
+        };
+
+        let rest = self.rest_days();
+        let character = &mut self.character;
+        let level_before = character.level;
+        let streak_before = self.achievements.progress.current_streak;
+        let titles_before = self.achievements.get_unlocked_titles(character);
+
+        let declared = task.get_challenge();
+        let started_at = task.get_start_date().filter(|start| *start <= completion_time);
+
+        // Quests finished far faster than their rating are rewarded as rated by time
+        let minutes = started_at.map(|start| (completion_time - start).num_seconds() as f64 / 60.0);
+        let xp_challenge = calibration::capped_challenge(&self.config.calibration, declared, minutes);
+        let challenge = xp_challenge.unwrap_or(declared);
+        let timing = XPCalculator::determine_timing(task.get_due_date(), completion_time);
+
+        // Stat bonuses are based on the stats before this quest trains them
+        let bonuses = self.config.stat_effects.bonuses(&character.stats);
+
+        // Permanent prestige bonus on top of the base rewards
+        let focus_xp = focus_minutes.map_or(0, |m| timewarrior::focus_xp(&self.config.timewarrior, m));
+        let mut xp = Self::quest_xp(character, &bonuses, challenge, task.get_urgency(), timing);
+        let mut gold = GoldCalculator::itemise(challenge);
+        gold.multiply(format!("prestige rank {}", character.prestige_rank), PrestigeSystem::gold_multiplier(character.prestige_rank));
+
+        // Trivial and instantly completed quests pay less, loot included
+        let farming = &self.config.farming;
+        let trivial_multiplier = farming.level_multiplier(character.level, challenge);
+        let instant_multiplier = farming.instant_multiplier(task.get_entry_date(), completion_time)
+            .filter(|_| instant_rule);
+        let reward_multiplier = trivial_multiplier.unwrap_or(1.0) * instant_multiplier.unwrap_or(1.0);
+        for breakdown in [&mut xp, &mut gold] {
+            if let Some(multiplier) = trivial_multiplier {
+                breakdown.multiply(format!("trivial at level {}", character.level), multiplier);
+            }
+            if let Some(multiplier) = instant_multiplier {
+                breakdown.multiply("completed right after it was added", multiplier);
+            }
+        }
+
+        // Habits pay more the stronger they are
+        let habit_strength = habits::strength_before(&self.config.habits, &rest, &self.history, task, completion_time.date_naive());
+        let habit_bonus = habit_strength.map_or(0.0, |strength| self.config.habits.bonus(strength));
+        let habit_xp = (xp.total() as f64 * habit_bonus).round() as u32;
+        let habit_gold = (gold.total() as f64 * habit_bonus).round() as u32;
+        let habit_source = format!("habit strength {:.0}%", habit_strength.unwrap_or(0.0) * 100.0);
+        xp.add(&habit_source, habit_xp);
+        gold.add(&habit_source, habit_gold);
+        xp.add(format!("focus time {:.0} min", focus_minutes.unwrap_or(0.0)), focus_xp);
+        let (xp_earned, gold_earned) = (xp.total(), gold.total());
+
+        let loot_roll = LootSystem::roll(challenge, bonuses.loot_chance_bonus, reward_multiplier);
+        let loot = loot_roll.drop.clone();
+        let mut achievements = Vec::new();
+        let bonus_gold = match loot {
+            Some(LootDrop::Gold(amount)) => amount,
+            _ => 0,
+        };
+        if let Some(ref loot) = loot {
+            achievements.extend(self.achievements.record_loot_drop(loot, character));
+        }
+        gold.add("loot: gold drop", bonus_gold);
+        let breakdown = RewardBreakdown { xp, gold, loot: loot_roll };
+        let (xp, gold) = (xp_earned, gold_earned);
+
+        character.add_xp(xp);
+        character.add_gold(gold + bonus_gold);
+        let saved_gold = character.reserve_for_goals(gold + bonus_gold);
+
+        let stat1 = task.get_stat1();
+        let stat2 = task.get_stat2();
+        character.complete_task(challenge, stat1, stat2);
+
+        let project = task.get_project();
+        achievements.extend(self.achievements.record_quest_completion(
+            character,
+            challenge,
+            timing,
+            project.as_deref(),
+            completion_time.date_naive(),
+            MissedDays { grace_days: bonuses.streak_grace_days, rest: &rest },
+        ));
+        if let Some(ref focus) = focus_day {
+            achievements.extend(self.achievements.record_focus_day(completion_time.date_naive(), focus, character));
+        }
+
+        let record = CompletionRecord {
+            uuid: task.uuid.clone(),
+            description: task.description.clone(),
+            project,
+            started_at: started_at.map(|start| start.to_rfc3339()),
+            completed_at: completion_time.to_rfc3339(),
+            challenge: declared,
+            xp_challenge,
+            timing,
+            xp,
+            focus_xp,
+            focus_minutes: focus_minutes.map(|m| m.round() as u32),
+            habit: task.habit_id(),
+            recur: task.recur.clone(),
+            habit_xp,
+            habit_gold,
+            trivial_multiplier,
+            instant_multiplier,
+            gold,
+            bonus_gold,
+            loot,
+            stat1,
+            stat2,
+            level_after: character.level,
+            breakdown: Some(breakdown),
+        };
+        self.history.record(record.clone());
+
+        let new_titles = self.achievements.get_unlocked_titles(character)
+            .into_iter()
+            .filter(|title| !titles_before.iter().any(|t| t.id == title.id))
+            .collect();
+        let streak_after = self.achievements.progress.current_streak;
+
+        Completion {
+            record,
+            saved_gold,
+            level_before,
+            achievements,
+            new_titles,
+            streak_broken: (streak_after < streak_before).then_some(streak_before),
+        }
+    }
+
+    /// Credit a focus session of `minutes` (out of `planned_minutes`) on a
+    /// task. None when it was too short to count.
+    pub fn record_focus_session(
+        &mut self,
+        task: &TaskData,
+        planned_minutes: u32,
+        minutes: u32,
+        started_at: DateTime<Utc>,
+    ) -> Option<FocusOutcome> {
+        let config = &self.config.focus;
+        if minutes < config.min_minutes.max(1) {
+            return None;
+        }
+        let minutes = minutes.min(planned_minutes);
+        let character = &mut self.character;
+        let level_before = character.level;
+
+        let xp = config.session_xp(minutes);
+        let (stat1, stat2) = (task.get_stat1(), task.get_stat2());
+        character.add_xp(xp);
+        character.train_stats(config.stat_gain(task.get_challenge(), minutes), stat1, stat2);
+
+        let completed = minutes >= planned_minutes;
+        // Short sessions can't farm the session count achievements
+        let full = completed && planned_minutes >= config.min_full_session;
+        let achievements = self.achievements.record_focus_session(minutes, full, started_at.date_naive(), character);
+
+        let session = FocusSession {
+            uuid: task.uuid.clone(),
+            description: task.description.clone(),
+            project: task.get_project(),
+            started_at: started_at.to_rfc3339(),
+            planned_minutes,
+            minutes,
+            completed,
+            xp,
+            stat1,
+            stat2,
+            level_after: character.level,
+        };
+        self.history.record_session(session.clone());
+
+        Some(FocusOutcome { session, level_before, achievements })
+    }
+
+    /// Timewarrior intervals from `since` on, when the integration is
+    /// enabled. They are read again only when an earlier day is asked for.
+    /// A failure to read them is reported and counts as no tracked time.
+    pub fn intervals(&mut self, since: NaiveDate) -> Option<&[Interval]> {
+        if !self.config.timewarrior.enabled {
+            return None;
+        }
+        let covered = matches!(self.intervals, Some((from, _)) if from.is_none_or(|from| from <= since));
+        if !covered {
+            let intervals = timewarrior::load(&self.config.timewarrior, Some(since)).unwrap_or_else(|e| {
+                eprintln!("Warning: Failed to read Timewarrior data: {}", e);
+                Vec::new()
+            });
+            self.intervals = Some((Some(since), intervals));
+        }
+        self.intervals.as_ref().map(|(_, intervals)| intervals.as_slice())
+    }
+
+    /// Use these intervals instead of reading Timewarrior (e.g. from an
+    /// exported file); enables the integration for this profile
+    pub fn set_intervals(&mut self, intervals: Vec<Interval>) {
+        self.config.timewarrior.enabled = true;
+        self.intervals = Some((None, intervals));
+    }
+
+    /// Credit a day's tracked time toward the deep work achievements
+    pub fn record_focus_day(&mut self, day: chrono::NaiveDate) -> Option<(DailyFocus, Vec<&'static Achievement>)> {
+        let focus = timewarrior::daily_focus(self.intervals(day)?, day, Utc::now());
+        let achievements = self.achievements.record_focus_day(day, &focus, &self.character);
+        Some((focus, achievements))
+    }
+
+    /// Weekly rest days from config plus the character's rest periods
+    pub fn rest_days(&self) -> RestDays {
+        RestDays::new(&self.config.rest, &self.character.rest_periods)
+    }
+
+    /// XP a task would earn if it were completed now (loot aside), taking
+    /// its challenge as declared
+    pub fn projected_xp(&self, task: &TaskData) -> u32 {
+        let bonuses = self.config.stat_effects.bonuses(&self.character.stats);
+        let timing = XPCalculator::determine_timing(task.get_due_date(), Utc::now());
+        let mut xp = Self::quest_xp(&self.character, &bonuses, task.get_challenge(), task.get_urgency(), timing);
+        if let Some(multiplier) = self.config.farming.level_multiplier(self.character.level, task.get_challenge()) {
+            xp.multiply("trivial", multiplier);
+        }
+        xp.total()
+    }
+
+    /// Challenge for a task added without one, from config and history
+    pub fn suggest_challenge(&self, task: &TaskData) -> Suggestion {
+        challenge::suggest(&self.config.challenge, &self.history, task)
+    }
+
+    fn quest_xp(character: &Character, bonuses: &StatBonuses, challenge: u8, urgency: f64, timing: TaskTiming) -> Breakdown {
+        let mut xp = XPCalculator::itemise(challenge, urgency, timing, bonuses);
+        xp.multiply(format!("prestige rank {}", character.prestige_rank), PrestigeSystem::xp_multiplier(character.prestige_rank));
+        xp
+    }
+
+    /// Character state the shop prices depend on
+    pub fn price_context(&self) -> PriceContext {
+        let recent_income = if self.shop.pricing.mode == PricingMode::Income {
+            let since = Utc::now() - chrono::Duration::days(self.shop.pricing.income_window_days as i64);
+            self.history.since(since).map(|r| r.total_gold()).sum()
+        } else {
+            0
+        };
+
+        PriceContext {
+            level: self.character.level,
+            recent_income,
+            discount: self.config.stat_effects.bonuses(&self.character.stats).shop_discount,
+        }
+    }
+
+    /// Buy a reward by ID or name at its current price. Gold reserved for
+    /// other rewards' savings goals can't be spent.
+    pub fn purchase(&mut self, reward_identifier: &str) -> Result<Purchase> {
+        let reward_id = self.shop.find_reward_id(reward_identifier)?;
+        let reward = self.shop.get_reward(reward_id).context("Reward not found")?;
+        let reward_name = reward.name.clone();
+
+        let ctx = self.price_context();
+        let price = self.shop.cost_of(reward, &ctx);
+        let spendable = self.character.spendable_gold_for(reward_id);
+        if price > spendable && price <= self.character.gold {
+            anyhow::bail!(
+                "Costs {} gold but only {} is spendable; {} is reserved for savings goals. \
+                 Free it with 'taskquest goal release <reward>'",
+                price, spendable, self.character.gold - spendable
+            );
+        }
+
+        let cost = self.shop.purchase_reward(reward_id, self.character.gold, &ctx)?;
+        self.character.gold -= cost;
+        // Buying the reward closes its goal, even one that wasn't fully funded
+        let goal_completed = self.character.savings_goals.iter()
+            .any(|goal| goal.reward_id == reward_id && goal.is_complete());
+        self.character.savings_goals.retain(|goal| goal.reward_id != reward_id);
+        let achievements = self.achievements.record_reward_purchase(&self.character);
+
+        Ok(Purchase {
+            reward_id,
+            reward_name,
+            cost,
+            gold_after: self.character.gold,
+            goal_completed,
+            achievements,
+        })
+    }
+
+    /// Delete a reward from the shop, releasing any gold saved for it.
+    /// Default rewards need `force` (see [`RewardStore::remove_reward`]).
+    pub fn remove_reward(&mut self, reward_identifier: &str, force: bool) -> Result<RemovedReward> {
+        let reward_id = self.shop.find_reward_id(reward_identifier)?;
+        let reward_name = self.shop.get_reward(reward_id).context("Reward not found")?.name.clone();
+
+        self.shop.remove_reward(reward_id, force)?;
+        let released_gold = self.character.release_goal(reward_id, None).ok();
+
+        Ok(RemovedReward { reward_id, reward_name, released_gold })
+    }
+
+    /// Display an unlocked title, by id or name
+    pub fn set_title(&mut self, query: &str) -> Result<&'static Title> {
+        let title = Title::find(query)
+            .with_context(|| format!("Title '{}' not found", query))?;
+        if !self.achievements.is_title_unlocked(title, &self.character) {
+            anyhow::bail!("Title '{}' is locked ({})", title.name, title.requirement());
+        }
+
+        self.character.active_title = Some(title.name.to_string());
+        Ok(title)
+    }
+
+    pub fn progress(&self) -> Progress {
+        let character = &self.character;
+        Progress {
+            name: character.name.clone(),
+            class: character.class,
+            title: character.active_title.clone(),
+            level: character.level,
+            total_xp: character.total_xp,
+            xp_to_next_level: character.xp_to_next_level(),
+            xp_progress_percent: character.xp_progress_percent(),
+            gold: character.gold,
+            reserved_gold: character.reserved_gold(),
+            tasks_completed: character.tasks_completed,
+            prestige_rank: character.prestige_rank,
+            current_streak: self.achievements.progress.current_streak,
+            longest_streak: self.achievements.progress.longest_streak,
+            achievements_unlocked: self.achievements.unlocked.len(),
+            achievements_total: ACHIEVEMENTS.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character::LevelSystem;
+    use tempfile::tempdir;
+
+    fn profile_in(dir: &Path, gold: u32) -> Profile {
+        let mut character = Character::new("Aria".to_string(), Class::Ranger);
+        character.gold = gold;
+        safe_write(&dir.join("character.json"), &character).unwrap();
+        Profile::open(dir).unwrap()
+    }
+
+    #[test]
+    fn test_purchase_respects_savings_goals() {
+        let dir = tempdir().unwrap();
+        let mut profile = profile_in(dir.path(), 100);
+        profile.character.savings_goals.push(crate::shop::SavingsGoal {
+            reward_id: 10,
+            reward_name: "Epic Reward".to_string(),
+            target: 3000,
+            rule: crate::shop::SavingsRule::Percent(10),
+            reserved: 60,
+        });
+
+        // Coffee Break (50) would eat into the 60 reserved gold
+        assert!(profile.purchase("Coffee Break").is_err());
+        profile.character.release_goal(10, Some(10)).unwrap();
+
+        let purchase = profile.purchase("Coffee Break").unwrap();
+        assert_eq!(purchase.cost, 50);
+        assert_eq!(purchase.gold_after, 50);
+        assert!(!purchase.goal_completed);
+        assert_eq!(profile.shop.purchases.len(), 1);
+    }
+
+    #[test]
+    fn test_goal_completed_only_when_fully_funded() {
+        let dir = tempdir().unwrap();
+        let mut profile = profile_in(dir.path(), 200);
+        let goal = |reserved| crate::shop::SavingsGoal {
+            reward_id: 1,
+            reward_name: "Coffee Break".to_string(),
+            target: 50,
+            rule: crate::shop::SavingsRule::Percent(10),
+            reserved,
+        };
+
+        // A partial contribution closes the goal but doesn't complete it
+        profile.character.savings_goals.push(goal(20));
+        let partial = profile.purchase("Coffee Break").unwrap();
+        assert!(!partial.goal_completed);
+        assert!(profile.character.savings_goals.is_empty());
+
+        profile.character.savings_goals.push(goal(50));
+        assert!(profile.purchase("Coffee Break").unwrap().goal_completed);
+    }
+
+    #[test]
+    fn test_calibration_caps_rushed_quests() {
+        let dir = tempdir().unwrap();
+        let mut profile = profile_in(dir.path(), 0);
+        profile.config.calibration.adjust_rewards = true;
+
+        let task = TaskData::from_json(r#"{
+            "uuid": "1",
+            "status": "completed",
+            "description": "Legendary one-minute quest",
+            "challenge": 10,
+            "start": "20260101T120000Z",
+            "end": "20260101T120100Z"
+        }"#).unwrap();
+        let record = profile.complete_task(&task).record;
+
+        assert_eq!(record.challenge, 10);
+        assert_eq!(record.xp_challenge, Some(4));
+        assert_eq!(record.duration_minutes(), Some(1.0));
+        assert!(record.gold <= 24); // 4 × 5 gold ± 20%
+    }
+
+    #[test]
+    fn test_focus_time_bonus_and_deep_work() {
+        let dir = tempdir().unwrap();
+        let mut profile = profile_in(dir.path(), 0);
+        profile.set_intervals(vec![
+            Interval {
+                start: "2026-01-05T08:00:00Z".parse().unwrap(),
+                end: Some("2026-01-05T10:30:00Z".parse().unwrap()),
+                tags: vec!["Write report".to_string()],
+            },
+            Interval {
+                start: "2026-01-05T11:00:00Z".parse().unwrap(),
+                end: Some("2026-01-05T13:00:00Z".parse().unwrap()),
+                tags: vec!["email".to_string()],
+            },
+        ]);
+
+        let task = TaskData::from_json(r#"{
+            "uuid": "1",
+            "status": "completed",
+            "description": "Write report",
+            "entry": "20260105T070000Z",
+            "end": "20260105T160000Z"
+        }"#).unwrap();
+        let completion = profile.complete_task(&task);
+
+        assert_eq!(completion.record.focus_minutes, Some(150));
+        assert_eq!(completion.record.focus_xp, 25);
+        let unlocked: Vec<_> = completion.achievements.iter().map(|a| a.id).collect();
+        assert!(unlocked.contains(&"deep_diver") && unlocked.contains(&"flow_state"));
+        assert!(!unlocked.contains(&"monk_mode"));
+    }
+
+    #[test]
+    fn test_intervals_are_read_from_a_start_day() {
+        let dir = tempdir().unwrap();
+        let db = dir.path().join("timew");
+        std::fs::create_dir_all(db.join("data")).unwrap();
+        std::fs::write(db.join("data/2026-01.data"), "inc 20260110T090000Z - 20260110T100000Z # old\n").unwrap();
+        std::fs::write(db.join("data/2026-03.data"), "inc 20260305T090000Z - 20260305T100000Z # new\n").unwrap();
+
+        let mut profile = profile_in(dir.path(), 0);
+        profile.config.timewarrior.enabled = true;
+        profile.config.timewarrior.source = timewarrior::TimewSource::Files;
+        profile.config.timewarrior.db = Some(db);
+
+        let march = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        assert_eq!(profile.intervals(march).unwrap().len(), 1);
+        // An earlier day reads the files again
+        let january = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        assert_eq!(profile.intervals(january).unwrap().len(), 2);
+        assert_eq!(profile.intervals(march).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_habit_rewards_grow_with_strength() {
+        let dir = tempdir().unwrap();
+        let mut profile = profile_in(dir.path(), 0);
+        let instance = |day: u32| TaskData::from_json(&format!(r#"{{
+            "uuid": "run-{day}", "status": "completed", "description": "Morning run",
+            "challenge": 4, "recur": "daily", "parent": "RUN", "end": "202603{day:02}T070000Z"
+        }}"#)).unwrap();
+
+        let bonuses: Vec<_> = [1, 2, 3].iter()
+            .map(|day| profile.complete_task(&instance(*day)).record.habit_xp)
+            .collect();
+        assert_eq!(bonuses[0], 0);
+        assert!(bonuses[1] > 0 && bonuses[2] > bonuses[1]);
+
+        // A skipped week costs some strength, but not all of it
+        let after_break = profile.complete_task(&instance(10)).record;
+        assert!(after_break.habit_xp < bonuses[2] && after_break.habit_xp > 0);
+        assert_eq!(after_break.habit.as_deref(), Some("run"));
+    }
+
+    #[test]
+    fn test_trivial_and_instant_quests_pay_less() {
+        let dir = tempdir().unwrap();
+        let mut profile = profile_in(dir.path(), 0);
+        let task = |uuid: &str, challenge: u8, entry: &str| TaskData::from_json(&format!(r#"{{
+            "uuid": "{uuid}", "status": "completed", "description": "Tidy desk", "challenge": {challenge},
+            "entry": "{entry}", "end": "20260301T120000Z"
+        }}"#)).unwrap();
+
+        let fresh = profile.complete_task(&task("a", 1, "20260301T090000Z")).record;
+        assert_eq!((fresh.trivial_multiplier, fresh.instant_multiplier), (None, None));
+        assert_eq!(fresh.xp, 10);
+
+        profile.character.add_xp(LevelSystem::xp_for_level(40));
+        let trivial = profile.complete_task(&task("b", 1, "20260301T090000Z")).record;
+        assert_eq!(trivial.trivial_multiplier, Some(0.1));
+        assert_eq!(trivial.xp, 1);
+        assert_eq!(profile.projected_xp(&task("c", 1, "20260301T090000Z")), 1);
+
+        let instant = profile.complete_task(&task("d", 10, "20260301T115930Z")).record;
+        assert_eq!((instant.trivial_multiplier, instant.instant_multiplier), (None, Some(0.5)));
+        assert_eq!(instant.xp, 50);
+
+        // `task log` sets entry and end together; that isn't farming
+        let logged = profile.complete_logged_task(&task("e", 10, "20260301T120000Z")).record;
+        assert_eq!(logged.instant_multiplier, None);
+        assert_eq!(logged.xp, 100);
+    }
+
+    #[test]
+    fn test_reward_breakdown() {
+        let dir = tempdir().unwrap();
+        let mut profile = profile_in(dir.path(), 0);
+        profile.character.prestige_rank = 1;
+        let task = TaskData::from_json(r#"{
+            "uuid": "1", "status": "completed", "description": "Ship release", "challenge": 5,
+            "urgency": 0.6, "due": "20260305T120000Z", "end": "20260301T120000Z"
+        }"#).unwrap();
+
+        let record = profile.complete_task(&task).record;
+        let breakdown = record.breakdown.as_ref().unwrap();
+        let sources: Vec<_> = breakdown.xp.lines.iter().map(|l| l.source.as_str()).collect();
+        assert_eq!(sources, ["challenge 5 × 10", "urgency 0.6", "timing: early", "prestige rank 1"]);
+        assert_eq!(breakdown.xp.total(), record.xp);
+        assert_eq!(breakdown.gold.total(), record.total_gold());
+        assert_eq!(breakdown.loot.drop.is_some(), record.loot.is_some());
+
+        assert_eq!(profile.history.find("1").unwrap().xp, record.xp);
+        assert!(profile.history.find("2").is_err());
+    }
+
+    #[test]
+    fn test_focus_sessions() {
+        let dir = tempdir().unwrap();
+        let mut profile = profile_in(dir.path(), 0);
+        let task = TaskData::from_json(r#"{
+            "uuid": "1", "status": "pending", "description": "Study", "challenge": 8, "stat1": "int"
+        }"#).unwrap();
+        let now = Utc::now();
+
+        assert!(profile.record_focus_session(&task, 25, 3, now).is_none());
+
+        let full = profile.record_focus_session(&task, 25, 25, now).unwrap();
+        assert!(full.session.completed);
+        assert_eq!(full.session.xp, 10);
+        assert!(full.achievements.iter().any(|a| a.id == "first_focus"));
+
+        let partial = profile.record_focus_session(&task, 25, 10, now).unwrap();
+        assert!(!partial.session.completed);
+        assert_eq!(partial.session.xp, 4);
+
+        assert_eq!(profile.character.total_xp, 14);
+        assert_eq!(profile.character.tasks_completed, 0);
+        assert!(profile.character.stats.intelligence > 10.0);
+        assert_eq!(profile.history.sessions.len(), 2);
+        assert_eq!(profile.achievements.progress.sessions_completed, 1);
+        assert_eq!(profile.achievements.progress.session_minutes, 35);
+    }
+
+    #[test]
+    fn test_short_sessions_dont_count_toward_session_achievements() {
+        let dir = tempdir().unwrap();
+        let mut profile = profile_in(dir.path(), 0);
+        let task = TaskData::from_json(r#"{"uuid": "1", "status": "pending", "description": "Study"}"#).unwrap();
+        let now = Utc::now();
+
+        for _ in 0..8 {
+            let short = profile.record_focus_session(&task, 5, 5, now).unwrap();
+            assert!(short.session.completed);
+            assert!(short.achievements.iter().all(|a| a.id != "focus_marathon"));
+        }
+        assert_eq!(profile.achievements.progress.sessions_completed, 0);
+        assert_eq!(profile.achievements.progress.most_sessions_in_day, 0);
+        assert_eq!(profile.achievements.progress.session_minutes, 40);
+    }
+
+    #[test]
+    fn test_progress() {
+        let dir = tempdir().unwrap();
+        let profile = profile_in(dir.path(), 25);
+        let progress = profile.progress();
+
+        assert_eq!(progress.name, "Aria");
+        assert_eq!(progress.level, 1);
+        assert_eq!(progress.gold, 25);
+        assert_eq!(progress.achievements_unlocked, 0);
+        assert_eq!(progress.achievements_total, ACHIEVEMENTS.len());
+    }
+}
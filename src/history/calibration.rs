@@ -0,0 +1,242 @@
+//! Difficulty calibration: how long quests really took compared with their
+//! declared challenge.
+//!
+//! A quest's duration comes from Taskwarrior's `start` and `end`. Each duration
+//! maps to the challenge the Challenge Guidelines give that much work, and the
+//! difference to the declared challenge is the quest's offset. The median
+//! offset per project shows where challenges are consistently rated too high
+//! or too low.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::{CompletionHistory, CompletionRecord};
+
+/// Durations (minutes) and the challenge they imply, following the Challenge
+/// Guidelines; interpolated on a log scale in between
+const GUIDELINE: [(f64, f64); 6] = [
+    (1.0, 1.0),
+    (5.0, 2.5),
+    (15.0, 4.5),
+    (60.0, 6.5),
+    (180.0, 8.5),
+    (480.0, 10.0),
+];
+
+/// `calibration` section of config.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CalibrationConfig {
+    /// Timed quests needed before a project is judged
+    pub min_samples: usize,
+    /// Median offset (challenge points) at which a project is flagged
+    pub flag_offset: f64,
+    /// Durations above this are ignored (tasks left started)
+    pub max_minutes: u32,
+    /// Cap the challenge rewards are based on for quests finished far faster
+    /// than their rating
+    pub adjust_rewards: bool,
+    /// Points a quest may be rated above its duration before the cap applies
+    pub tolerance: u8,
+}
+
+impl Default for CalibrationConfig {
+    fn default() -> Self {
+        Self {
+            min_samples: 5,
+            flag_offset: 2.0,
+            max_minutes: 1440,
+            adjust_rewards: false,
+            tolerance: 3,
+        }
+    }
+}
+
+/// Challenge the guidelines give a quest that took this long
+pub fn implied_challenge(minutes: f64) -> f64 {
+    let (first, last) = (GUIDELINE[0], GUIDELINE[GUIDELINE.len() - 1]);
+    if minutes <= first.0 {
+        return first.1;
+    }
+    if minutes >= last.0 {
+        return last.1;
+    }
+
+    GUIDELINE.windows(2)
+        .find(|w| minutes <= w[1].0)
+        .map(|w| {
+            let ((m0, c0), (m1, c1)) = (w[0], w[1]);
+            c0 + (c1 - c0) * (minutes / m0).ln() / (m1 / m0).ln()
+        })
+        .unwrap_or(last.1)
+}
+
+/// Challenge to base rewards on when a quest was rated well above how long it
+/// took, or None when the declared challenge stands.
+///
+/// Only the fixed guideline is used here, not learned calibration: rewards
+/// that followed the learned offsets could be trained upward by farming.
+pub fn capped_challenge(config: &CalibrationConfig, declared: u8, minutes: Option<f64>) -> Option<u8> {
+    if !config.adjust_rewards {
+        return None;
+    }
+    let minutes = minutes.filter(|m| *m <= config.max_minutes as f64)?;
+    let cap = (implied_challenge(minutes) + config.tolerance as f64).ceil() as u8;
+    (declared > cap).then_some(cap.max(1))
+}
+
+/// Offset of a timed quest: positive when rated harder than it took
+fn offset(config: &CalibrationConfig, record: &CompletionRecord) -> Option<(f64, f64)> {
+    let minutes = record.duration_minutes()
+        .filter(|m| *m <= config.max_minutes as f64)?;
+    Some((minutes, record.challenge as f64 - implied_challenge(minutes)))
+}
+
+/// How a group of quests was rated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    NotEnoughData,
+    Calibrated,
+    /// Challenges are higher than the time spent suggests
+    RatedTooHigh,
+    /// Challenges are lower than the time spent suggests
+    RatedTooLow,
+}
+
+/// Calibration of one project (or of all quests)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calibration {
+    /// None for the overall calibration
+    pub project: Option<String>,
+    pub samples: usize,
+    pub median_minutes: f64,
+    pub median_challenge: f64,
+    /// Median of declared minus implied challenge
+    pub offset: f64,
+    pub verdict: Verdict,
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+fn calibration(config: &CalibrationConfig, project: Option<String>, records: &[&CompletionRecord]) -> Calibration {
+    let timed: Vec<_> = records.iter()
+        .filter_map(|r| offset(config, r).map(|(minutes, offset)| (r.challenge as f64, minutes, offset)))
+        .collect();
+
+    let offset = median(&mut timed.iter().map(|t| t.2).collect::<Vec<_>>());
+    let verdict = if timed.len() < config.min_samples.max(1) {
+        Verdict::NotEnoughData
+    } else if offset >= config.flag_offset {
+        Verdict::RatedTooHigh
+    } else if offset <= -config.flag_offset {
+        Verdict::RatedTooLow
+    } else {
+        Verdict::Calibrated
+    };
+
+    Calibration {
+        project,
+        samples: timed.len(),
+        median_minutes: median(&mut timed.iter().map(|t| t.1).collect::<Vec<_>>()),
+        median_challenge: median(&mut timed.iter().map(|t| t.0).collect::<Vec<_>>()),
+        offset,
+        verdict,
+    }
+}
+
+/// Calibration over all timed quests, then per project (projects without
+/// any timed quests are left out)
+pub fn calibrate(config: &CalibrationConfig, history: &CompletionHistory) -> (Calibration, Vec<Calibration>) {
+    let all: Vec<_> = history.completions.iter().collect();
+    let overall = calibration(config, None, &all);
+
+    let mut by_project: BTreeMap<&str, Vec<&CompletionRecord>> = BTreeMap::new();
+    for record in &all {
+        if let Some(ref project) = record.project {
+            by_project.entry(project).or_default().push(record);
+        }
+    }
+    let projects = by_project.into_iter()
+        .map(|(project, records)| calibration(config, Some(project.to_string()), &records))
+        .filter(|c| c.samples > 0)
+        .collect();
+
+    (overall, projects)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(project: &str, challenge: u8, minutes: i64) -> CompletionRecord {
+        let end = chrono::DateTime::parse_from_rfc3339("2026-01-01T12:00:00+00:00").unwrap();
+        CompletionRecord {
+            uuid: format!("{}-{}", project, minutes),
+            description: "Quest".to_string(),
+            project: Some(project.to_string()),
+            started_at: Some((end - chrono::Duration::minutes(minutes)).to_rfc3339()),
+            completed_at: end.to_rfc3339(),
+            challenge,
+            level_after: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_implied_challenge_follows_guidelines() {
+        assert_eq!(implied_challenge(0.5), 1.0);
+        assert_eq!(implied_challenge(15.0), 4.5);
+        assert!((implied_challenge(30.0) - 5.5).abs() < 1e-9);
+        assert_eq!(implied_challenge(600.0), 10.0);
+    }
+
+    #[test]
+    fn test_flags_consistently_misrated_projects() {
+        let config = CalibrationConfig::default();
+        let mut history = CompletionHistory::new();
+        for minutes in [2, 3, 5, 4, 6] {
+            history.record(record("chores", 8, minutes)); // quick, rated hard
+        }
+        for minutes in [55, 60, 70, 65, 50] {
+            history.record(record("work", 6, minutes));
+        }
+        history.record(record("garden", 2, 240));
+        let mut untimed = record("garden", 2, 0);
+        untimed.started_at = None;
+        history.record(untimed);
+
+        let (overall, projects) = calibrate(&config, &history);
+        assert_eq!(overall.samples, 11);
+        let verdicts: Vec<_> = projects.iter()
+            .map(|c| (c.project.as_deref().unwrap(), c.samples, c.verdict))
+            .collect();
+        assert_eq!(verdicts, [
+            ("chores", 5, Verdict::RatedTooHigh),
+            ("garden", 1, Verdict::NotEnoughData),
+            ("work", 5, Verdict::Calibrated),
+        ]);
+    }
+
+    #[test]
+    fn test_capped_challenge() {
+        let mut config = CalibrationConfig::default();
+        assert_eq!(capped_challenge(&config, 10, Some(1.0)), None); // off by default
+
+        config.adjust_rewards = true;
+        assert_eq!(capped_challenge(&config, 10, Some(1.0)), Some(4));
+        assert_eq!(capped_challenge(&config, 8, Some(30.0)), None);
+        assert_eq!(capped_challenge(&config, 10, None), None);
+        assert_eq!(capped_challenge(&config, 10, Some(5000.0)), None); // left started
+    }
+}
@@ -0,0 +1,376 @@
+//! Habits: recurring Taskwarrior tasks.
+//!
+//! Every instance of a recurring task carries its template's UUID in
+//! `parent`, which ties the instances' completions together into one habit.
+//! A habit's strength grows with each period it is done and decays gently
+//! for each period skipped, and completing it earns more the stronger it is.
+//! Rest days pause daily habits; rest periods pause every habit.
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::CompletionHistory;
+use crate::character::RestDays;
+use crate::taskwarrior::challenge::parse_estimate;
+use crate::taskwarrior::TaskData;
+
+/// Periods shown in a habit's streak chart
+pub const CHART_PERIODS: usize = 14;
+
+/// `habits` section of config.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HabitConfig {
+    /// Share of the way to full strength gained for each period done
+    pub growth: f64,
+    /// Share of strength lost for each period skipped
+    pub decay: f64,
+    /// Extra XP and gold at full strength, as a share of the quest's rewards
+    pub max_bonus: f64,
+}
+
+impl Default for HabitConfig {
+    fn default() -> Self {
+        Self {
+            growth: 0.1,
+            decay: 0.1,
+            max_bonus: 0.5,
+        }
+    }
+}
+
+impl HabitConfig {
+    /// Extra rewards for a habit of this strength, as a share of the base
+    pub fn bonus(&self, strength: f64) -> f64 {
+        self.max_bonus * strength.clamp(0.0, 1.0)
+    }
+}
+
+/// How often a habit recurs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recurrence {
+    Days(u32),
+    /// Monday to Friday
+    Weekdays,
+}
+
+impl std::str::FromStr for Recurrence {
+    type Err = anyhow::Error;
+
+    /// Taskwarrior's named periods (`daily`, `weekly`, `monthly`, ...) or a
+    /// duration (`3d`, `2wk`, `P1M`). Periods shorter than a day count as daily.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let days = match s.trim().to_lowercase().as_str() {
+            "weekdays" => return Ok(Recurrence::Weekdays),
+            "daily" | "day" => 1,
+            "weekly" | "week" | "sennight" => 7,
+            "biweekly" | "fortnight" => 14,
+            "monthly" | "month" => 30,
+            "quarterly" => 91,
+            "semiannual" => 183,
+            "annual" | "yearly" | "year" => 365,
+            "biannual" | "biyearly" => 730,
+            other => parse_estimate(other)
+                .map(|minutes| (minutes / 1440).max(1))
+                .ok_or_else(|| anyhow::anyhow!("Unknown recurrence: {}", s))?,
+        };
+        Ok(Recurrence::Days(days))
+    }
+}
+
+impl Recurrence {
+    /// Whole periods from one day to a later one. Daily habits skip rest
+    /// days; longer periods only skip rest periods, since every week has the
+    /// same weekly rest days.
+    fn periods_between(self, from: NaiveDate, to: NaiveDate, rest: &RestDays) -> u32 {
+        match self {
+            Recurrence::Days(1) => rest.active_days_between(from, to).max(0) as u32,
+            Recurrence::Days(days) => {
+                let elapsed = (to - from).num_days() - rest.period_days_between(from, to);
+                (elapsed as f64 / days as f64).round().max(0.0) as u32
+            }
+            Recurrence::Weekdays => from.iter_days()
+                .skip(1)
+                .take_while(|day| *day <= to)
+                .filter(|day| is_weekday(*day) && !rest.is_rest_day(*day))
+                .count() as u32,
+        }
+    }
+
+    /// A period that can't be skipped because it is all rest
+    fn is_rest(self, rest: &RestDays, start: NaiveDate, end: NaiveDate) -> bool {
+        let mut days = start.iter_days().take_while(|day| *day <= end);
+        match self {
+            Recurrence::Days(1) | Recurrence::Weekdays => days.all(|day| rest.is_rest_day(day)),
+            Recurrence::Days(_) => days.all(|day| rest.in_period(day)),
+        }
+    }
+
+    /// Days of the period `back` periods before the one containing `today`
+    fn period(self, today: NaiveDate, back: u32) -> (NaiveDate, NaiveDate) {
+        match self {
+            Recurrence::Days(days) => {
+                let end = today - Duration::days((back * days) as i64);
+                (end - Duration::days(days as i64 - 1), end)
+            }
+            Recurrence::Weekdays => {
+                let mut day = today;
+                for _ in 0..back {
+                    day = day.pred_opt().unwrap_or(day);
+                    while !is_weekday(day) {
+                        day = day.pred_opt().unwrap_or(day);
+                    }
+                }
+                (day, day)
+            }
+        }
+    }
+}
+
+fn is_weekday(day: NaiveDate) -> bool {
+    !matches!(day.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+/// Strength and streaks after walking a habit's completion days up to `today`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Standing {
+    /// 0.0 (new or long abandoned) to 1.0
+    pub strength: f64,
+    /// Periods done in a row, still running as of `today`
+    pub streak: u32,
+    pub best_streak: u32,
+}
+
+/// Walk a habit's completion days (oldest first). Completions in the same
+/// period as the previous one don't strengthen it again. The period `today`
+/// falls in isn't counted as skipped until it is over.
+pub fn standing(config: &HabitConfig, recurrence: Recurrence, rest: &RestDays, days: &[NaiveDate], today: NaiveDate) -> Standing {
+    // Out-of-range settings in config.json would push strength outside 0..=1
+    let growth = config.growth.clamp(0.0, 1.0);
+    let keep = 1.0 - config.decay.clamp(0.0, 1.0);
+    let mut standing = Standing { strength: 0.0, streak: 0, best_streak: 0 };
+    let mut last: Option<NaiveDate> = None;
+
+    for &day in days.iter().filter(|day| **day <= today) {
+        if let Some(previous) = last {
+            let periods = recurrence.periods_between(previous, day, rest);
+            if periods == 0 {
+                continue;
+            }
+            standing.strength *= keep.powi(periods as i32 - 1);
+            standing.streak = if periods == 1 { standing.streak + 1 } else { 1 };
+        } else {
+            standing.streak = 1;
+        }
+        standing.strength += growth * (1.0 - standing.strength);
+        standing.best_streak = standing.best_streak.max(standing.streak);
+        last = Some(day);
+    }
+
+    if let Some(previous) = last {
+        let skipped = recurrence.periods_between(previous, today, rest).saturating_sub(1);
+        if skipped > 0 {
+            standing.strength *= keep.powi(skipped as i32);
+            standing.streak = 0;
+        }
+    }
+    standing
+}
+
+/// Days a habit was completed on, oldest first
+fn completion_days(history: &CompletionHistory, id: &str) -> Vec<NaiveDate> {
+    let mut days: Vec<_> = history.completions.iter()
+        .filter(|r| r.habit.as_deref() == Some(id))
+        .filter_map(|r| r.completed_at())
+        .map(|t| t.date_naive())
+        .collect();
+    days.sort();
+    days
+}
+
+fn task_standing(
+    config: &HabitConfig,
+    rest: &RestDays,
+    history: &CompletionHistory,
+    task: &TaskData,
+    day: NaiveDate,
+    before: bool,
+) -> Option<Standing> {
+    let id = task.habit_id()?;
+    let recurrence: Recurrence = task.recur.as_deref()?.parse().ok()?;
+    let days: Vec<_> = completion_days(history, &id).into_iter()
+        .filter(|d| if before { *d < day } else { *d <= day })
+        .collect();
+    Some(standing(config, recurrence, rest, &days, day))
+}
+
+/// Strength of a task's habit before completing it on `day`, or None for
+/// tasks that don't recur
+pub fn strength_before(config: &HabitConfig, rest: &RestDays, history: &CompletionHistory, task: &TaskData, day: NaiveDate) -> Option<f64> {
+    task_standing(config, rest, history, task, day, true).map(|s| s.strength)
+}
+
+/// Where a task's habit stands on `day`, counting that day's completions
+pub fn standing_on(config: &HabitConfig, rest: &RestDays, history: &CompletionHistory, task: &TaskData, day: NaiveDate) -> Option<Standing> {
+    task_standing(config, rest, history, task, day, false)
+}
+
+/// A habit and how it's going
+#[derive(Debug, Clone, PartialEq)]
+pub struct Habit {
+    /// UUID of the recurring template
+    pub id: String,
+    pub description: String,
+    pub recur: String,
+    /// None when the recurrence couldn't be read
+    pub recurrence: Option<Recurrence>,
+    pub completions: usize,
+    pub last_done: Option<NaiveDate>,
+    pub standing: Standing,
+    /// Whether each of the last `CHART_PERIODS` periods was done, oldest
+    /// first; None for periods before the habit was first done and rests
+    pub chart: Vec<Option<bool>>,
+}
+
+/// Every habit in the history, plus recurring templates not completed yet.
+/// Strongest first.
+pub fn habits(
+    config: &HabitConfig,
+    rest: &RestDays,
+    history: &CompletionHistory,
+    templates: &[TaskData],
+    today: NaiveDate,
+) -> Vec<Habit> {
+    // Latest description and recurrence of each habit
+    let mut known: BTreeMap<String, (String, String)> = BTreeMap::new();
+    for record in &history.completions {
+        if let (Some(id), Some(recur)) = (&record.habit, &record.recur) {
+            known.insert(id.clone(), (record.description.clone(), recur.clone()));
+        }
+    }
+    for template in templates {
+        if let (Some(id), Some(recur)) = (template.habit_id(), &template.recur) {
+            known.insert(id, (template.description.clone(), recur.clone()));
+        }
+    }
+
+    let mut habits: Vec<Habit> = known.into_iter()
+        .map(|(id, (description, recur))| {
+            let days = completion_days(history, &id);
+            let recurrence = recur.parse::<Recurrence>().ok();
+            let standing = recurrence
+                .map(|r| standing(config, r, rest, &days, today))
+                .unwrap_or(Standing { strength: 0.0, streak: 0, best_streak: 0 });
+            let chart = recurrence
+                .map(|r| (0..CHART_PERIODS as u32).rev()
+                    .map(|back| {
+                        let (start, end) = r.period(today, back);
+                        let done = days.iter().any(|d| (start..=end).contains(d));
+                        let started = days.first().is_some_and(|first| *first <= end);
+                        (started && (done || !r.is_rest(rest, start, end))).then_some(done)
+                    })
+                    .collect())
+                .unwrap_or_default();
+
+            Habit {
+                id,
+                description,
+                recur,
+                recurrence,
+                completions: days.len(),
+                last_done: days.last().copied(),
+                standing,
+                chart,
+            }
+        })
+        .collect();
+
+    habits.sort_by(|a, b| b.standing.strength.total_cmp(&a.standing.strength));
+    habits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, d).unwrap()
+    }
+
+    #[test]
+    fn test_parse_recurrence() {
+        assert_eq!("daily".parse::<Recurrence>().unwrap(), Recurrence::Days(1));
+        assert_eq!("weekdays".parse::<Recurrence>().unwrap(), Recurrence::Weekdays);
+        assert_eq!("3d".parse::<Recurrence>().unwrap(), Recurrence::Days(3));
+        assert_eq!("2wk".parse::<Recurrence>().unwrap(), Recurrence::Days(14));
+        assert_eq!("P1M".parse::<Recurrence>().unwrap(), Recurrence::Days(30));
+        assert_eq!("PT4H".parse::<Recurrence>().unwrap(), Recurrence::Days(1));
+        assert!("sometimes".parse::<Recurrence>().is_err());
+    }
+
+    #[test]
+    fn test_strength_grows_and_decays() {
+        let config = HabitConfig::default();
+        let rest = RestDays::default();
+        let daily = Recurrence::Days(1);
+
+        let week: Vec<_> = (1..=7).map(day).collect();
+        let done = standing(&config, daily, &rest, &week, day(7));
+        assert!((done.strength - (1.0 - 0.9f64.powi(7))).abs() < 1e-9);
+        assert_eq!((done.streak, done.best_streak), (7, 7));
+
+        // Today isn't over yet, so the streak still stands
+        assert_eq!(standing(&config, daily, &rest, &week, day(8)).streak, 7);
+
+        // Two skipped days: strength decays, the streak restarts
+        let skipped = standing(&config, daily, &rest, &week, day(10));
+        assert_eq!(skipped.streak, 0);
+        assert!((skipped.strength - done.strength * 0.81).abs() < 1e-9);
+
+        // Doing it twice in a day doesn't count twice
+        let twice = [day(1), day(1), day(2)];
+        assert_eq!(standing(&config, daily, &rest, &twice, day(2)).streak, 2);
+    }
+
+    #[test]
+    fn test_strength_stays_in_range() {
+        let config = HabitConfig { growth: 1.5, decay: -0.5, ..HabitConfig::default() };
+        let rest = RestDays::default();
+        let days: Vec<_> = (1..=3).map(day).collect();
+
+        let done = standing(&config, Recurrence::Days(1), &rest, &days, day(3));
+        assert_eq!(done.strength, 1.0);
+        let skipped = standing(&config, Recurrence::Days(1), &rest, &days, day(10));
+        assert_eq!(skipped.strength, 1.0);
+    }
+
+    #[test]
+    fn test_weekdays_skip_weekends() {
+        let config = HabitConfig::default();
+        let rest = RestDays::default();
+        // 2026-03-06 is a Friday
+        let days = [day(5), day(6), day(9)];
+        let s = standing(&config, Recurrence::Weekdays, &rest, &days, day(9));
+        assert_eq!(s.streak, 3);
+        assert_eq!(Recurrence::Weekdays.period(day(9), 1), (day(6), day(6)));
+    }
+
+    #[test]
+    fn test_rest_pauses_decay() {
+        let config = HabitConfig::default();
+        let rest = RestDays {
+            weekly: vec![Weekday::Sat, Weekday::Sun],
+            periods: vec![crate::character::RestPeriod { start: "2026-03-09".to_string(), end: None }],
+        };
+        let days = [day(5), day(6)];
+        let before = standing(&config, Recurrence::Days(1), &rest, &days, day(6));
+
+        // Weekend, then an open-ended rest: nothing skipped
+        assert_eq!(standing(&config, Recurrence::Days(1), &rest, &days, day(20)), before);
+
+        // A weekly habit only pauses for the rest period
+        let weekly = standing(&config, Recurrence::Days(7), &rest, &[day(1)], day(30));
+        assert_eq!(weekly.streak, 1);
+    }
+}
@@ -0,0 +1,220 @@
+pub mod report;
+pub mod calibration;
+pub mod habits;
+
+pub use report::{Report, ReportGrouping, ReportPeriod};
+pub use calibration::CalibrationConfig;
+pub use habits::HabitConfig;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::character::StatType;
+use crate::progression::{LootDrop, RewardBreakdown, TaskTiming};
+
+/// Everything a single completed quest earned
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompletionRecord {
+    pub uuid: String,
+    pub description: String,
+    pub project: Option<String>,
+    pub started_at: Option<String>, // ISO 8601 datetime, if the task was started
+    pub completed_at: String, // ISO 8601 datetime
+    pub challenge: u8,
+    /// Challenge rewards were based on, when calibration capped the declared one
+    pub xp_challenge: Option<u8>,
+    pub timing: TaskTiming,
+    pub xp: u32,
+    /// Part of `xp` earned for focus time tracked in Timewarrior
+    pub focus_xp: u32,
+    pub focus_minutes: Option<u32>,
+    /// UUID of the recurring template, for habits
+    pub habit: Option<String>,
+    pub recur: Option<String>,
+    /// Parts of `xp` and `gold` earned for the habit's strength
+    pub habit_xp: u32,
+    pub habit_gold: u32,
+    /// Share of the rewards paid because the quest was trivial for the
+    /// character's level, when reduced
+    pub trivial_multiplier: Option<f64>,
+    /// Share paid because the task was completed right after being added
+    pub instant_multiplier: Option<f64>,
+    pub gold: u32,
+    pub bonus_gold: u32,
+    pub loot: Option<LootDrop>,
+    pub stat1: Option<StatType>,
+    pub stat2: Option<StatType>,
+    pub level_after: u32,
+    /// Itemised XP, gold and loot roll
+    pub breakdown: Option<RewardBreakdown>,
+}
+
+impl CompletionRecord {
+    pub fn completed_at(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.completed_at)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc))
+    }
+
+    /// Minutes from start to completion, for tasks that were started
+    pub fn duration_minutes(&self) -> Option<f64> {
+        let started = DateTime::parse_from_rfc3339(self.started_at.as_deref()?).ok()?;
+        let minutes = (self.completed_at()? - started.with_timezone(&Utc)).num_seconds() as f64 / 60.0;
+        (minutes >= 0.0).then_some(minutes)
+    }
+
+    /// Total gold including loot gold
+    pub fn total_gold(&self) -> u32 {
+        self.gold + self.bonus_gold
+    }
+}
+
+/// A focus session run with `taskquest focus`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FocusSession {
+    pub uuid: String,
+    pub description: String,
+    pub project: Option<String>,
+    pub started_at: String, // ISO 8601 datetime
+    pub planned_minutes: u32,
+    pub minutes: u32,
+    /// Ran the full planned time
+    pub completed: bool,
+    pub xp: u32,
+    pub stat1: Option<StatType>,
+    pub stat2: Option<StatType>,
+    pub level_after: u32,
+}
+
+impl FocusSession {
+    pub fn started_at(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.started_at)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc))
+    }
+}
+
+/// What to measure when charting activity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivityMetric {
+    Xp,
+    Tasks,
+}
+
+impl std::str::FromStr for ActivityMetric {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "xp" => Ok(ActivityMetric::Xp),
+            "tasks" => Ok(ActivityMetric::Tasks),
+            _ => Err(anyhow::anyhow!("Invalid metric: {}. Use: xp or tasks", s)),
+        }
+    }
+}
+
+/// Log of every quest TaskQuest has credited, stored in `history.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletionHistory {
+    #[serde(default)]
+    pub schema_version: u32,
+    pub completions: Vec<CompletionRecord>,
+    #[serde(default)]
+    pub sessions: Vec<FocusSession>,
+}
+
+impl Default for CompletionHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CompletionHistory {
+    pub fn new() -> Self {
+        Self {
+            schema_version: <Self as crate::storage::Versioned>::SCHEMA_VERSION,
+            completions: Vec::new(),
+            sessions: Vec::new(),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+
+        crate::storage::load_with_backup(path)
+            .context("Failed to load history")
+    }
+
+    pub fn record(&mut self, record: CompletionRecord) {
+        self.completions.push(record);
+    }
+
+    pub fn record_session(&mut self, session: FocusSession) {
+        self.sessions.push(session);
+    }
+
+    /// XP or quest count per day
+    pub fn daily_totals(&self, metric: ActivityMetric) -> BTreeMap<NaiveDate, u32> {
+        let mut totals = BTreeMap::new();
+        for record in &self.completions {
+            if let Some(time) = record.completed_at() {
+                let value = match metric {
+                    ActivityMetric::Xp => record.xp,
+                    ActivityMetric::Tasks => 1,
+                };
+                *totals.entry(time.date_naive()).or_insert(0) += value;
+            }
+        }
+        // Focus sessions earn XP but aren't quests
+        if metric == ActivityMetric::Xp {
+            for session in &self.sessions {
+                if let Some(time) = session.started_at() {
+                    *totals.entry(time.date_naive()).or_insert(0) += session.xp;
+                }
+            }
+        }
+        totals
+    }
+
+    /// Character level at the end of each active day
+    pub fn level_by_day(&self) -> BTreeMap<NaiveDate, u32> {
+        let mut latest: BTreeMap<NaiveDate, (DateTime<Utc>, u32)> = BTreeMap::new();
+        for record in &self.completions {
+            if let Some(time) = record.completed_at() {
+                let entry = latest.entry(time.date_naive()).or_insert((time, record.level_after));
+                if time >= entry.0 {
+                    *entry = (time, record.level_after);
+                }
+            }
+        }
+        latest.into_iter().map(|(day, (_, level))| (day, level)).collect()
+    }
+
+    /// The completion of a task, by UUID or a unique UUID prefix
+    pub fn find(&self, uuid: &str) -> Result<&CompletionRecord> {
+        let uuid = uuid.to_lowercase();
+        if let Some(record) = self.completions.iter().rev().find(|r| r.uuid.to_lowercase() == uuid) {
+            return Ok(record);
+        }
+
+        let matches: Vec<_> = self.completions.iter()
+            .filter(|r| r.uuid.to_lowercase().starts_with(&uuid))
+            .collect();
+        match matches.as_slice() {
+            [record] => Ok(record),
+            [] => anyhow::bail!("No completed quest with UUID {}", uuid),
+            _ => anyhow::bail!("{} quests have a UUID starting with {}. Give more of it", matches.len(), uuid),
+        }
+    }
+
+    /// Completions at or after `since`
+    pub fn since(&self, since: DateTime<Utc>) -> impl Iterator<Item = &CompletionRecord> {
+        self.completions.iter()
+            .filter(move |r| r.completed_at().is_some_and(|t| t >= since))
+    }
+}
@@ -0,0 +1,230 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::{CompletionHistory, CompletionRecord};
+use crate::progression::TaskTiming;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportPeriod {
+    Week,
+    Month,
+    Year,
+}
+
+impl ReportPeriod {
+    pub fn duration(&self) -> Duration {
+        match self {
+            ReportPeriod::Week => Duration::days(7),
+            ReportPeriod::Month => Duration::days(30),
+            ReportPeriod::Year => Duration::days(365),
+        }
+    }
+}
+
+impl std::str::FromStr for ReportPeriod {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "week" => Ok(ReportPeriod::Week),
+            "month" => Ok(ReportPeriod::Month),
+            "year" => Ok(ReportPeriod::Year),
+            _ => Err(anyhow::anyhow!("Invalid period: {}. Use: week, month, or year", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportGrouping {
+    Project,
+    Day,
+    Stat,
+    Challenge,
+}
+
+impl ReportGrouping {
+    /// Groups a completion belongs to. Quests train two stats, so by stat a
+    /// quest counts once under each of them.
+    fn keys(&self, record: &CompletionRecord) -> Vec<String> {
+        match self {
+            ReportGrouping::Project => vec![record.project.clone().unwrap_or_else(|| "(none)".to_string())],
+            ReportGrouping::Day => vec![record.completed_at.get(..10).unwrap_or("unknown").to_string()],
+            ReportGrouping::Stat => {
+                let mut stats: Vec<String> = record.stat1.iter().chain(record.stat2.iter())
+                    .map(|s| format!("{:?}", s))
+                    .collect();
+                stats.dedup();
+                if stats.is_empty() {
+                    stats.push("(none)".to_string());
+                }
+                stats
+            }
+            // Zero-padded so challenge 10 sorts after 9
+            ReportGrouping::Challenge => vec![format!("{:02}", record.challenge)],
+        }
+    }
+}
+
+impl std::str::FromStr for ReportGrouping {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "project" => Ok(ReportGrouping::Project),
+            "day" => Ok(ReportGrouping::Day),
+            "stat" => Ok(ReportGrouping::Stat),
+            "challenge" => Ok(ReportGrouping::Challenge),
+            _ => Err(anyhow::anyhow!("Invalid grouping: {}. Use: project, day, stat, or challenge", s)),
+        }
+    }
+}
+
+/// Totals for one group (or the whole period)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReportRow {
+    pub key: String,
+    pub completions: u32,
+    pub xp: u32,
+    pub gold: u32,
+    pub loot_drops: u32,
+    pub with_due_date: u32,
+    pub on_time: u32,
+}
+
+impl ReportRow {
+    fn add(&mut self, record: &CompletionRecord) {
+        self.completions += 1;
+        self.xp += record.xp;
+        self.gold += record.total_gold();
+        if record.loot.is_some() {
+            self.loot_drops += 1;
+        }
+        if record.timing != TaskTiming::NoDueDate {
+            self.with_due_date += 1;
+            if matches!(record.timing, TaskTiming::Early | TaskTiming::OnTime) {
+                self.on_time += 1;
+            }
+        }
+    }
+
+    /// Share of quests with a due date finished on time or early (0.0 to 1.0)
+    pub fn on_time_rate(&self) -> Option<f64> {
+        if self.with_due_date == 0 {
+            None
+        } else {
+            Some(self.on_time as f64 / self.with_due_date as f64)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub since: String,
+    pub until: String,
+    pub rows: Vec<ReportRow>,
+    pub total: ReportRow,
+}
+
+impl Report {
+    /// Summarise completions in the period ending at `now`
+    pub fn build(
+        history: &CompletionHistory,
+        period: ReportPeriod,
+        grouping: ReportGrouping,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let since = now - period.duration();
+        let mut groups: BTreeMap<String, ReportRow> = BTreeMap::new();
+        let mut total = ReportRow { key: "TOTAL".to_string(), ..Default::default() };
+
+        for record in history.since(since) {
+            for key in grouping.keys(record) {
+                groups.entry(key.clone())
+                    .or_insert_with(|| ReportRow { key, ..Default::default() })
+                    .add(record);
+            }
+            total.add(record);
+        }
+
+        Self {
+            since: since.to_rfc3339(),
+            until: now.to_rfc3339(),
+            rows: groups.into_values().collect(),
+            total,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::progression::LootDrop;
+
+    fn record(days_ago: i64, project: &str, challenge: u8, timing: TaskTiming, now: DateTime<Utc>) -> CompletionRecord {
+        CompletionRecord {
+            uuid: format!("uuid-{}-{}", days_ago, challenge),
+            description: "Test".to_string(),
+            project: Some(project.to_string()),
+            completed_at: (now - Duration::days(days_ago)).to_rfc3339(),
+            challenge,
+            timing,
+            xp: challenge as u32 * 10,
+            gold: challenge as u32 * 5,
+            loot: if challenge > 5 { Some(LootDrop::Gold(10)) } else { None },
+            level_after: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_report_by_project() {
+        let now = Utc::now();
+        let mut history = CompletionHistory::new();
+        history.record(record(1, "work", 5, TaskTiming::OnTime, now));
+        history.record(record(2, "work", 8, TaskTiming::Late, now));
+        history.record(record(3, "home", 2, TaskTiming::NoDueDate, now));
+        history.record(record(40, "home", 2, TaskTiming::NoDueDate, now)); // outside week
+
+        let report = Report::build(&history, ReportPeriod::Week, ReportGrouping::Project, now);
+
+        assert_eq!(report.rows.len(), 2);
+        let work = report.rows.iter().find(|r| r.key == "work").unwrap();
+        assert_eq!(work.completions, 2);
+        assert_eq!(work.xp, 130);
+        assert_eq!(work.loot_drops, 1);
+        assert_eq!(work.on_time_rate(), Some(0.5));
+
+        assert_eq!(report.total.completions, 3);
+        let home = report.rows.iter().find(|r| r.key == "home").unwrap();
+        assert_eq!(home.on_time_rate(), None);
+    }
+
+    #[test]
+    fn test_report_by_stat_counts_both_stats() {
+        use crate::character::StatType;
+
+        let now = Utc::now();
+        let mut history = CompletionHistory::new();
+        history.record(CompletionRecord { stat1: Some(StatType::INT), stat2: Some(StatType::WIS), ..record(1, "a", 5, TaskTiming::NoDueDate, now) });
+        history.record(CompletionRecord { stat1: Some(StatType::INT), ..record(2, "a", 2, TaskTiming::NoDueDate, now) });
+        history.record(record(3, "a", 3, TaskTiming::NoDueDate, now));
+
+        let report = Report::build(&history, ReportPeriod::Week, ReportGrouping::Stat, now);
+        let rows: Vec<_> = report.rows.iter().map(|r| (r.key.as_str(), r.completions, r.xp)).collect();
+        assert_eq!(rows, vec![("(none)", 1, 30), ("INT", 2, 70), ("WIS", 1, 50)]);
+        assert_eq!(report.total.completions, 3);
+    }
+
+    #[test]
+    fn test_report_by_challenge_sorted() {
+        let now = Utc::now();
+        let mut history = CompletionHistory::new();
+        history.record(record(1, "a", 10, TaskTiming::NoDueDate, now));
+        history.record(record(1, "a", 9, TaskTiming::NoDueDate, now));
+
+        let report = Report::build(&history, ReportPeriod::Month, ReportGrouping::Challenge, now);
+        let keys: Vec<_> = report.rows.iter().map(|r| r.key.as_str()).collect();
+        assert_eq!(keys, vec!["09", "10"]);
+    }
+}
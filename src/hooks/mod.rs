@@ -1,16 +1,75 @@
 use anyhow::Result;
 use std::io::{self, Read};
-use crate::taskwarrior::{TaskData, TaskwarriorIntegration};
+use serde_json::{json, Value};
+use crate::game::{Completion, Profile};
+use crate::history::CompletionHistory;
+use crate::taskwarrior::{challenge, uda, ChallengeConfig, TaskData, TaskwarriorIntegration};
+use crate::storage::profiles::{self, ProfileConfig};
+
+/// Taskwarrior passes `data:<path>` among the hook arguments
+fn task_data_location() -> Option<String> {
+    std::env::args()
+        .find_map(|arg| arg.strip_prefix("data:").map(|s| s.to_string()))
+}
+
+/// Route the task to a profile using the rules in profiles.json
+fn select_profile_for_task(task: &TaskData) -> Result<()> {
+    let base_dir = profiles::base_dir()?;
+    let config = ProfileConfig::load(&base_dir)?;
+    let location = task_data_location();
+
+    if let Some(profile) = config.profile_for_task(task.project.as_deref(), location.as_deref()) {
+        profiles::select(profile)?;
+    }
+
+    Ok(())
+}
+
+/// The profile a task belongs to. None when there is no character yet.
+fn profile_for_task(task: &TaskData) -> Option<Profile> {
+    if let Err(e) = select_profile_for_task(task) {
+        eprintln!("Warning: Failed to select profile: {}", e);
+    }
+    Profile::open_active().ok()
+}
+
+/// Store what a completed quest earned on the task itself
+fn write_rewards(task: &mut Value, completion: &Completion) {
+    let record = &completion.record;
+    task[uda::XP] = json!(record.xp);
+    task[uda::GOLD] = json!(record.total_gold());
+    if let Some(ref loot) = record.loot {
+        task[uda::LOOT] = json!(loot.to_string());
+    }
+}
 
 pub fn on_add_hook() -> Result<()> {
     // Read task from stdin
     let mut input = String::new();
     io::stdin().read_to_string(&mut input)?;
 
-    // Set default challenge if not set
-    let mut task_data: serde_json::Value = serde_json::from_str(&input)?;
-    if task_data.get("challenge").is_none() {
-        task_data["challenge"] = serde_json::json!(5);
+    let mut task_data: Value = serde_json::from_str(&input)?;
+    let Ok(mut task) = serde_json::from_value::<TaskData>(task_data.clone()) else {
+        if task_data.get("challenge").is_none() {
+            task_data["challenge"] = json!(ChallengeConfig::default().default);
+        }
+        println!("{}", serde_json::to_string(&task_data)?);
+        return Ok(());
+    };
+    let profile = profile_for_task(&task);
+
+    // Suggest a challenge if not set
+    if task.challenge.is_none() {
+        let suggestion = match profile {
+            Some(ref profile) => profile.suggest_challenge(&task),
+            None => challenge::suggest(&ChallengeConfig::default(), &CompletionHistory::new(), &task),
+        };
+        task.challenge = Some(suggestion.challenge);
+        task_data["challenge"] = json!(suggestion.challenge);
+    }
+
+    if let Some(ref profile) = profile {
+        task_data[uda::XP] = json!(profile.projected_xp(&task));
     }
 
     // Output modified task
@@ -30,18 +89,34 @@ pub fn on_modify_hook() -> Result<()> {
         return Ok(());
     }
 
-    let _original: TaskData = serde_json::from_str(lines[0])?;
-    let modified: TaskData = serde_json::from_str(lines[1])?;
+    let original: TaskData = serde_json::from_str(lines[0])?;
+    let mut task_data: Value = serde_json::from_str(lines[1])?;
+    let mut modified: TaskData = serde_json::from_value(task_data.clone())?;
 
-    // Check if task was completed
-    if modified.is_completed() {
-        if let Err(e) = TaskwarriorIntegration::process_completion(&modified) {
-            eprintln!("Warning: Failed to process task completion: {}", e);
+    // Finishing an active task may drop `start`; the original still has it
+    if modified.start.is_none() {
+        modified.start = original.start.clone();
+    }
+
+    if modified.is_completed() && !original.is_completed() {
+        if let Err(e) = select_profile_for_task(&modified) {
+            eprintln!("Warning: Failed to select profile: {}", e);
+        }
+
+        match TaskwarriorIntegration::process_completion(&modified) {
+            Ok(Some(completion)) => write_rewards(&mut task_data, &completion),
+            Ok(None) => {}
+            Err(e) => eprintln!("Warning: Failed to process task completion: {}", e),
+        }
+    } else if modified.status == "pending" {
+        // Keep the projection current as challenge, due date or stats change
+        if let Some(profile) = profile_for_task(&modified) {
+            task_data[uda::XP] = json!(profile.projected_xp(&modified));
         }
     }
 
-    // Output modified task unchanged
-    println!("{}", lines[1]);
+    // Output the task with any rewards written back
+    println!("{}", serde_json::to_string(&task_data)?);
 
     Ok(())
 }
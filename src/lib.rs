@@ -0,0 +1,45 @@
+//! TaskQuest: a gamified RPG layer for Taskwarrior.
+//!
+//! The [`Profile`] API covers the common cases: load a profile, credit a
+//! completed task, buy a reward, query progress and save it again.
+//!
+//! ```no_run
+//! use taskquest::{Profile, TaskData};
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let mut profile = Profile::open_active()?;
+//!
+//! let task = TaskData::from_json(r#"{"uuid":"a1","status":"completed","description":"Write report","challenge":6}"#)?;
+//! let completion = profile.complete_task(&task);
+//! println!("+{} XP", completion.record.xp);
+//!
+//! profile.save()?;
+//! println!("Level {}", profile.progress().level);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The modules below expose the building blocks (XP and gold formulas,
+//! levels, achievements, the shop) for tools that need finer control.
+//! `display` and `hooks` are the `taskquest` binary's own front ends.
+
+pub mod character;
+pub mod progression;
+pub mod storage;
+pub mod taskwarrior;
+pub mod display;
+pub mod hooks;
+pub mod achievements;
+pub mod shop;
+pub mod sync;
+pub mod config;
+pub mod history;
+pub mod doctor;
+pub mod events;
+pub mod game;
+pub mod timewarrior;
+pub mod focus;
+
+pub use character::Character;
+pub use game::{Completion, FocusOutcome, Profile, Progress, Purchase};
+pub use taskwarrior::TaskData;
@@ -1,21 +1,12 @@
-mod character;
-mod progression;
-mod storage;
-mod taskwarrior;
-mod display;
-mod hooks;
-mod achievements;
-mod shop;
-mod sync;
-
 use anyhow::Result;
 use clap::Parser;
-use display::CLI;
+use taskquest::display::CLI;
+use taskquest::hooks;
 
 fn main() -> Result<()> {
     // Check if being run as a hook
     let args: Vec<String> = std::env::args().collect();
-    if args.len() > 0 {
+    if !args.is_empty() {
         let program_name = std::path::Path::new(&args[0])
             .file_name()
             .and_then(|n| n.to_str())
@@ -0,0 +1,124 @@
+//! Itemised rewards: how a quest's XP and gold were reached.
+//!
+//! A [`Breakdown`] starts from a base amount and lists every multiplier,
+//! bonus and random roll applied to it, with its source and the running
+//! amount. The final reward is the running amount rounded down.
+
+use serde::{Deserialize, Serialize};
+
+use super::LootDrop;
+
+/// What one line of a breakdown did to the amount
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Effect {
+    /// The starting amount
+    Base,
+    Multiply(f64),
+    Add(u32),
+    /// A random roll between `min` and `max`, replacing the amount
+    Roll { min: u32, max: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Line {
+    pub source: String,
+    pub effect: Effect,
+    /// Amount after this line
+    pub amount: f64,
+}
+
+/// Every step from a base amount to a reward
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Breakdown {
+    pub lines: Vec<Line>,
+}
+
+impl Breakdown {
+    pub fn new(source: impl Into<String>, base: u32) -> Self {
+        Self {
+            lines: vec![Line { source: source.into(), effect: Effect::Base, amount: base as f64 }],
+        }
+    }
+
+    /// Scale the amount. Multipliers of exactly 1 change nothing and are left out.
+    pub fn multiply(&mut self, source: impl Into<String>, factor: f64) {
+        if factor != 1.0 {
+            self.push(source, Effect::Multiply(factor), self.amount() * factor);
+        }
+    }
+
+    /// Add a flat bonus. Zero bonuses are left out.
+    pub fn add(&mut self, source: impl Into<String>, bonus: u32) {
+        if bonus > 0 {
+            self.push(source, Effect::Add(bonus), self.amount() + bonus as f64);
+        }
+    }
+
+    /// Replace the amount with a roll between `min` and `max`
+    pub fn roll(&mut self, source: impl Into<String>, min: u32, max: u32, rolled: u32) {
+        self.push(source, Effect::Roll { min, max }, rolled as f64);
+    }
+
+    /// Running amount after the last line
+    pub fn amount(&self) -> f64 {
+        self.lines.last().map_or(0.0, |line| line.amount)
+    }
+
+    /// The reward: the running amount rounded down
+    pub fn total(&self) -> u32 {
+        // Tolerate float error such as 130 coming out as 129.99999999999997
+        (self.amount() + 1e-9).max(0.0) as u32
+    }
+
+    fn push(&mut self, source: impl Into<String>, effect: Effect, amount: f64) {
+        self.lines.push(Line { source: source.into(), effect, amount });
+    }
+}
+
+/// How the loot roll went
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LootRoll {
+    /// 30% + challenge × 2%
+    pub base_chance: f64,
+    /// From DEX
+    pub bonus_chance: f64,
+    /// Trivial and instant quests drop less
+    pub multiplier: f64,
+    /// Final drop chance, 0 to 1
+    pub chance: f64,
+    /// Uniform roll in 0..1; loot drops when it's below `chance`
+    pub roll: f64,
+    pub drop: Option<LootDrop>,
+}
+
+/// Itemised XP, gold and loot of one quest, as saved in history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RewardBreakdown {
+    pub xp: Breakdown,
+    /// Quest gold, loot gold included
+    pub gold: Breakdown,
+    pub loot: LootRoll,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breakdown_running_amount() {
+        let mut breakdown = Breakdown::new("challenge 5 × 10", 50);
+        breakdown.multiply("urgency", 1.25);
+        breakdown.multiply("on time", 1.0);
+        breakdown.add("habit", 0);
+        breakdown.add("focus", 3);
+        assert_eq!(breakdown.lines.len(), 3);
+        assert_eq!(breakdown.total(), 65);
+
+        breakdown.roll("variance", 20, 30, 27);
+        assert_eq!(breakdown.total(), 27);
+
+        let mut breakdown = Breakdown::new("challenge 10 × 10", 100);
+        breakdown.multiply("tenths", 1.3);
+        assert_eq!(breakdown.total(), 130);
+    }
+}
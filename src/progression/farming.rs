@@ -0,0 +1,85 @@
+//! Diminishing rewards for farming trivial quests.
+//!
+//! Like monsters greying out in an MMO, a quest whose challenge is far below
+//! the character's level pays less XP and gold and drops loot less often.
+//! Tasks added and completed within moments of each other are scaled down too.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// `farming` section of config.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FarmingConfig {
+    /// Levels each challenge point covers: challenge 4 is on level up to
+    /// level 4 × this, plus `grace_levels`
+    pub levels_per_challenge: u32,
+    pub grace_levels: u32,
+    /// Reward lost per level beyond that
+    pub reduction_per_level: f64,
+    /// Trivial quests always pay at least this share
+    pub min_multiplier: f64,
+    /// Tasks completed within this many minutes of being added...
+    pub instant_minutes: u32,
+    /// ...pay this share of their rewards
+    pub instant_multiplier: f64,
+}
+
+impl Default for FarmingConfig {
+    fn default() -> Self {
+        Self {
+            levels_per_challenge: 5,
+            grace_levels: 15,
+            reduction_per_level: 0.05,
+            min_multiplier: 0.1,
+            instant_minutes: 2,
+            instant_multiplier: 0.5,
+        }
+    }
+}
+
+impl FarmingConfig {
+    /// Highest level at which a challenge still pays in full
+    pub fn full_reward_level(&self, challenge: u8) -> u32 {
+        challenge as u32 * self.levels_per_challenge + self.grace_levels
+    }
+
+    /// Share of the rewards a quest of this challenge pays at this level, or
+    /// None when it pays in full
+    pub fn level_multiplier(&self, level: u32, challenge: u8) -> Option<f64> {
+        let levels_over = level.checked_sub(self.full_reward_level(challenge)).filter(|l| *l > 0)?;
+        let multiplier = 1.0 - levels_over as f64 * self.reduction_per_level;
+        Some(multiplier.clamp(self.min_multiplier.clamp(0.0, 1.0), 1.0))
+    }
+
+    /// Share of the rewards for a task completed right after it was added, or
+    /// None when it took long enough (or its entry date is unknown)
+    pub fn instant_multiplier(&self, entry: Option<DateTime<Utc>>, end: DateTime<Utc>) -> Option<f64> {
+        let seconds = (end - entry?).num_seconds();
+        (seconds >= 0 && seconds < self.instant_minutes as i64 * 60)
+            .then_some(self.instant_multiplier.clamp(0.0, 1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trivial_quests_grey_out() {
+        let config = FarmingConfig::default();
+        assert_eq!(config.level_multiplier(40, 5), None);
+        assert_eq!(config.level_multiplier(20, 1), None);
+        assert!((config.level_multiplier(40, 3).unwrap() - 0.5).abs() < 1e-9);
+        assert_eq!(config.level_multiplier(40, 1), Some(0.1));
+    }
+
+    #[test]
+    fn test_instant_completion() {
+        let config = FarmingConfig::default();
+        let end = Utc::now();
+        assert_eq!(config.instant_multiplier(Some(end - chrono::Duration::seconds(30)), end), Some(0.5));
+        assert_eq!(config.instant_multiplier(Some(end - chrono::Duration::minutes(10)), end), None);
+        assert_eq!(config.instant_multiplier(None, end), None);
+    }
+}
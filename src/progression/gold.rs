@@ -1,5 +1,7 @@
 use rand::Rng;
 
+use super::Breakdown;
+
 pub struct GoldCalculator;
 
 impl GoldCalculator {
@@ -11,15 +13,21 @@ impl GoldCalculator {
     /// # Returns
     /// Gold amount with ±20% variance
     pub fn calculate(challenge: u8) -> u32 {
+        Self::itemise(challenge).total()
+    }
+
+    /// The same calculation, step by step
+    pub fn itemise(challenge: u8) -> Breakdown {
         // Base: challenge * 5
         let base = (challenge as u32) * 5;
+        let mut breakdown = Breakdown::new(format!("challenge {} × 5", challenge), base);
 
         // Random variance: ±20%
         let variance = (base as f64 * 0.2) as u32;
         let min = base.saturating_sub(variance);
         let max = base + variance;
-
-        rand::thread_rng().gen_range(min..=max)
+        breakdown.roll("variance ±20%", min, max, rand::thread_rng().gen_range(min..=max));
+        breakdown
     }
 }
 
@@ -32,19 +40,19 @@ mod tests {
         // Challenge 1: 4-6 gold
         for _ in 0..100 {
             let gold = GoldCalculator::calculate(1);
-            assert!(gold >= 4 && gold <= 6);
+            assert!((4..=6).contains(&gold));
         }
 
         // Challenge 5: 20-30 gold
         for _ in 0..100 {
             let gold = GoldCalculator::calculate(5);
-            assert!(gold >= 20 && gold <= 30);
+            assert!((20..=30).contains(&gold));
         }
 
         // Challenge 10: 40-60 gold
         for _ in 0..100 {
             let gold = GoldCalculator::calculate(10);
-            assert!(gold >= 40 && gold <= 60);
+            assert!((40..=60).contains(&gold));
         }
     }
 }
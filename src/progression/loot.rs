@@ -1,6 +1,8 @@
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
+use super::LootRoll;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum LootDrop {
     Gold(u32),
@@ -10,6 +12,15 @@ pub enum LootDrop {
     },
 }
 
+impl std::fmt::Display for LootDrop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LootDrop::Gold(amount) => write!(f, "{} gold", amount),
+            LootDrop::Reward { tier, name } => write!(f, "{} ({})", name, tier.name()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum RewardTier {
     Normal,    // Color: white
@@ -38,21 +49,45 @@ impl RewardTier {
     }
 }
 
+impl std::str::FromStr for RewardTier {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "normal" => Ok(RewardTier::Normal),
+            "heroic" => Ok(RewardTier::Heroic),
+            "epic" => Ok(RewardTier::Epic),
+            "legendary" => Ok(RewardTier::Legendary),
+            _ => Err(anyhow::anyhow!("Invalid tier. Use: normal, heroic, epic, or legendary")),
+        }
+    }
+}
+
 pub struct LootSystem;
 
 impl LootSystem {
     /// Roll for loot drop based on challenge level
     /// Base drop chance: 30% + (challenge * 2%)
-    pub fn roll_for_loot(challenge: u8) -> Option<LootDrop> {
-        let mut rng = rand::thread_rng();
+    /// plus any bonus chance (e.g. from DEX), scaled by `multiplier`
+    /// (trivial quests drop less)
+    pub fn roll_for_loot(challenge: u8, bonus_chance: f64, multiplier: f64) -> Option<LootDrop> {
+        Self::roll(challenge, bonus_chance, multiplier).drop
+    }
 
+    /// The same roll, with the chance and the number rolled
+    pub fn roll(challenge: u8, bonus_chance: f64, multiplier: f64) -> LootRoll {
         // Base drop chance: 30% + (challenge * 2%)
-        let drop_chance = 0.30 + (challenge as f64 * 0.02);
-
-        if rng.gen_bool(drop_chance.min(1.0)) {
-            Some(Self::determine_loot_type())
-        } else {
-            None
+        let base_chance = 0.30 + (challenge as f64 * 0.02);
+        let chance = ((base_chance + bonus_chance) * multiplier).clamp(0.0, 1.0);
+        let roll: f64 = rand::thread_rng().gen();
+
+        LootRoll {
+            base_chance,
+            bonus_chance,
+            multiplier,
+            chance,
+            roll,
+            drop: (roll < chance).then(Self::determine_loot_type),
         }
     }
 
@@ -150,7 +185,7 @@ mod tests {
 
         // Simulate 1000 drops
         for _ in 0..1000 {
-            if let Some(loot) = LootSystem::roll_for_loot(10) {
+            if let Some(loot) = LootSystem::roll_for_loot(10, 0.0, 1.0) {
                 match loot {
                     LootDrop::Gold(_) => gold_count += 1,
                     LootDrop::Reward { tier, .. } => match tier {
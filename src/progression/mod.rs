@@ -1,7 +1,13 @@
 pub mod xp;
 pub mod gold;
 pub mod loot;
+pub mod stat_effects;
+pub mod farming;
+pub mod breakdown;
 
 pub use xp::{XPCalculator, TaskTiming};
 pub use gold::GoldCalculator;
 pub use loot::{LootSystem, LootDrop, RewardTier};
+pub use stat_effects::{StatEffects, StatBonuses};
+pub use farming::FarmingConfig;
+pub use breakdown::{Breakdown, Effect, LootRoll, RewardBreakdown};
@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+use crate::character::stats::STAT_BASE;
+use crate::character::Stats;
+
+/// How strongly each stat feeds into the reward formulas.
+/// All values are per stat point above the base of 10.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatEffects {
+    /// INT: extra XP multiplier per point
+    pub int_xp_per_point: f64,
+    /// DEX: extra loot drop chance per point (absolute, 0.002 = +0.2%)
+    pub dex_loot_per_point: f64,
+    /// CHA: shop discount per point
+    pub cha_discount_per_point: f64,
+    /// CHA: maximum shop discount
+    pub max_cha_discount: f64,
+    /// CON: reduction of the Late timing penalty per point
+    pub con_relief_per_point: f64,
+    /// CON: maximum reduction of the Late timing penalty
+    pub max_con_relief: f64,
+    /// WIS: stat points needed for each missed day a streak survives
+    pub wis_points_per_grace_day: f64,
+    /// STR: extra XP multiplier per point on high-challenge quests
+    pub str_xp_per_point: f64,
+    /// STR: minimum challenge for the STR bonus to apply
+    pub str_min_challenge: u8,
+}
+
+impl Default for StatEffects {
+    fn default() -> Self {
+        Self {
+            int_xp_per_point: 0.005,
+            dex_loot_per_point: 0.002,
+            cha_discount_per_point: 0.004,
+            max_cha_discount: 0.30,
+            con_relief_per_point: 0.01,
+            max_con_relief: 0.30,
+            wis_points_per_grace_day: 20.0,
+            str_xp_per_point: 0.01,
+            str_min_challenge: 7,
+        }
+    }
+}
+
+/// Concrete bonuses a character's stats currently give
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StatBonuses {
+    pub xp_multiplier: f64,             // INT
+    pub loot_chance_bonus: f64,         // DEX
+    pub shop_discount: f64,             // CHA
+    pub late_penalty_relief: f64,       // CON
+    pub streak_grace_days: u32,         // WIS
+    pub high_challenge_multiplier: f64, // STR
+    pub high_challenge_min: u8,
+}
+
+impl StatBonuses {
+    /// Bonuses that change nothing
+    pub fn none() -> Self {
+        Self {
+            xp_multiplier: 1.0,
+            loot_chance_bonus: 0.0,
+            shop_discount: 0.0,
+            late_penalty_relief: 0.0,
+            streak_grace_days: 0,
+            high_challenge_multiplier: 1.0,
+            high_challenge_min: u8::MAX,
+        }
+    }
+
+    /// STR multiplier for a quest of this challenge
+    pub fn strength_multiplier(&self, challenge: u8) -> f64 {
+        if challenge >= self.high_challenge_min {
+            self.high_challenge_multiplier
+        } else {
+            1.0
+        }
+    }
+}
+
+impl StatEffects {
+    /// Work out the bonuses granted by a set of stats
+    pub fn bonuses(&self, stats: &Stats) -> StatBonuses {
+        let above_base = |value: f64| (value - STAT_BASE).max(0.0);
+
+        StatBonuses {
+            xp_multiplier: 1.0 + above_base(stats.intelligence) * self.int_xp_per_point,
+            loot_chance_bonus: above_base(stats.dexterity) * self.dex_loot_per_point,
+            shop_discount: (above_base(stats.charisma) * self.cha_discount_per_point)
+                .min(self.max_cha_discount),
+            late_penalty_relief: (above_base(stats.constitution) * self.con_relief_per_point)
+                .min(self.max_con_relief),
+            streak_grace_days: if self.wis_points_per_grace_day > 0.0 {
+                (above_base(stats.wisdom) / self.wis_points_per_grace_day).floor() as u32
+            } else {
+                0
+            },
+            high_challenge_multiplier: 1.0 + above_base(stats.strength) * self.str_xp_per_point,
+            high_challenge_min: self.str_min_challenge,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_stats_give_no_bonus() {
+        let bonuses = StatEffects::default().bonuses(&Stats::new());
+        let none = StatBonuses { high_challenge_min: 7, ..StatBonuses::none() };
+        assert_eq!(bonuses, none);
+    }
+
+    #[test]
+    fn test_trained_stats() {
+        let mut stats = Stats::new();
+        stats.intelligence = 30.0;
+        stats.charisma = 200.0;
+        stats.wisdom = 50.0;
+        stats.strength = 20.0;
+
+        let bonuses = StatEffects::default().bonuses(&stats);
+        assert!((bonuses.xp_multiplier - 1.10).abs() < 1e-9);
+        assert!((bonuses.shop_discount - 0.30).abs() < 1e-9); // capped
+        assert_eq!(bonuses.streak_grace_days, 2);
+        assert!((bonuses.strength_multiplier(8) - 1.10).abs() < 1e-9);
+        assert_eq!(bonuses.strength_multiplier(3), 1.0);
+    }
+}
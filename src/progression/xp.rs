@@ -1,14 +1,29 @@
 use chrono::{DateTime, Utc, Duration};
+use serde::{Deserialize, Serialize};
+use super::{Breakdown, StatBonuses};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TaskTiming {
     Early,       // >24hrs before due
     OnTime,      // Day of due date
     GracePeriod, // <24hrs late
     Late,        // >24hrs late
+    #[default]
     NoDueDate,   // No penalty
 }
 
+impl TaskTiming {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TaskTiming::Early => "early",
+            TaskTiming::OnTime => "on time",
+            TaskTiming::GracePeriod => "grace period",
+            TaskTiming::Late => "late",
+            TaskTiming::NoDueDate => "no due date",
+        }
+    }
+}
+
 pub struct XPCalculator;
 
 impl XPCalculator {
@@ -18,23 +33,39 @@ impl XPCalculator {
     /// * `challenge` - Task challenge rating (1-10)
     /// * `urgency` - Taskwarrior's urgency score
     /// * `timing` - When the task was completed relative to due date
-    pub fn calculate(challenge: u8, urgency: f64, timing: TaskTiming) -> u32 {
+    /// * `bonuses` - Stat bonuses: INT scales all XP, STR scales high-challenge
+    ///   quests and CON softens the `Late` penalty
+    pub fn calculate(challenge: u8, urgency: f64, timing: TaskTiming, bonuses: &StatBonuses) -> u32 {
+        Self::itemise(challenge, urgency, timing, bonuses).total()
+    }
+
+    /// The same calculation, step by step
+    pub fn itemise(challenge: u8, urgency: f64, timing: TaskTiming, bonuses: &StatBonuses) -> Breakdown {
         // Base XP scales with challenge
-        let base_xp = (challenge as u32) * 10;
+        let mut breakdown = Breakdown::new(format!("challenge {} × 10", challenge), challenge as u32 * 10);
 
         // Urgency modifier (1.0 to 1.5x)
         let urgency_multiplier = 1.0 + (urgency * 0.5).min(0.5);
+        breakdown.multiply(format!("urgency {:.1}", urgency), urgency_multiplier);
 
         // Timing bonus/penalty
         let timing_multiplier = match timing {
             TaskTiming::Early => 1.3,       // >24hrs before due
             TaskTiming::OnTime => 1.0,      // Day of due date
             TaskTiming::GracePeriod => 0.8, // <24hrs late
-            TaskTiming::Late => 0.5,        // >24hrs late
+            TaskTiming::Late => 0.5 + bonuses.late_penalty_relief, // >24hrs late
             TaskTiming::NoDueDate => 1.0,   // No penalty
         };
+        let timing_source = if timing == TaskTiming::Late && bonuses.late_penalty_relief > 0.0 {
+            format!("timing: {} (CON +{:.0}%)", timing.label(), bonuses.late_penalty_relief * 100.0)
+        } else {
+            format!("timing: {}", timing.label())
+        };
+        breakdown.multiply(timing_source, timing_multiplier);
 
-        ((base_xp as f64) * urgency_multiplier * timing_multiplier) as u32
+        breakdown.multiply("INT", bonuses.xp_multiplier);
+        breakdown.multiply(format!("STR (challenge {}+)", bonuses.high_challenge_min), bonuses.strength_multiplier(challenge));
+        breakdown
     }
 
     /// Determine task timing based on due date and completion time
@@ -67,16 +98,40 @@ mod tests {
     #[test]
     fn test_xp_calculation() {
         // Challenge 5, medium urgency, on time: ~75 XP
-        let xp = XPCalculator::calculate(5, 0.5, TaskTiming::OnTime);
-        assert!(xp >= 62 && xp <= 88); // 50 * 1.25 = 62.5, allowing for rounding
+        let xp = XPCalculator::calculate(5, 0.5, TaskTiming::OnTime, &StatBonuses::none());
+        assert!((62..=88).contains(&xp)); // 50 * 1.25 = 62.5, allowing for rounding
 
         // Challenge 10, high urgency, early: ~195 XP
-        let xp = XPCalculator::calculate(10, 1.0, TaskTiming::Early);
-        assert!(xp >= 180 && xp <= 210);
+        let xp = XPCalculator::calculate(10, 1.0, TaskTiming::Early, &StatBonuses::none());
+        assert!((180..=210).contains(&xp));
 
         // Challenge 3, low urgency, late: ~15 XP
-        let xp = XPCalculator::calculate(3, 0.1, TaskTiming::Late);
-        assert!(xp >= 10 && xp <= 20);
+        let xp = XPCalculator::calculate(3, 0.1, TaskTiming::Late, &StatBonuses::none());
+        assert!((10..=20).contains(&xp));
+    }
+
+    #[test]
+    fn test_stat_bonuses() {
+        let bonuses = StatBonuses {
+            xp_multiplier: 1.2,
+            late_penalty_relief: 0.3,
+            high_challenge_multiplier: 1.5,
+            high_challenge_min: 7,
+            ..StatBonuses::none()
+        };
+
+        // INT only: 50 * 1.2
+        assert_eq!(XPCalculator::calculate(5, 0.0, TaskTiming::NoDueDate, &bonuses), 60);
+        // INT and STR: 80 * 1.2 * 1.5
+        assert_eq!(XPCalculator::calculate(8, 0.0, TaskTiming::NoDueDate, &bonuses), 144);
+        // CON softens Late from 0.5 to 0.8: 50 * 0.8 * 1.2
+        assert_eq!(XPCalculator::calculate(5, 0.0, TaskTiming::Late, &bonuses), 48);
+
+        // Each multiplier is itemised with its source
+        let breakdown = XPCalculator::itemise(8, 0.0, TaskTiming::Late, &bonuses);
+        let sources: Vec<_> = breakdown.lines.iter().map(|l| l.source.as_str()).collect();
+        assert_eq!(sources, ["challenge 8 × 10", "timing: late (CON +30%)", "INT", "STR (challenge 7+)"]);
+        assert_eq!(breakdown.total(), 115);
     }
 
     #[test]
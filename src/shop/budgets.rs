@@ -0,0 +1,219 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::progression::RewardTier;
+use super::rewards::Reward;
+
+/// One completed purchase
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PurchaseRecord {
+    pub reward_id: u32,
+    pub reward_name: String,
+    pub tier: RewardTier,
+    pub cost: u32,
+    pub purchased_at: String, // ISO 8601 datetime
+    pub gold_after: u32,
+}
+
+impl PurchaseRecord {
+    pub fn purchased_at(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.purchased_at)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc))
+    }
+}
+
+/// Rolling window a budget applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetPeriod {
+    Day,
+    Week,
+    Month,
+}
+
+impl BudgetPeriod {
+    pub fn duration(&self) -> Duration {
+        match self {
+            BudgetPeriod::Day => Duration::days(1),
+            BudgetPeriod::Week => Duration::days(7),
+            BudgetPeriod::Month => Duration::days(30),
+        }
+    }
+}
+
+impl std::str::FromStr for BudgetPeriod {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "day" => Ok(BudgetPeriod::Day),
+            "week" => Ok(BudgetPeriod::Week),
+            "month" => Ok(BudgetPeriod::Month),
+            _ => Err(anyhow::anyhow!("Invalid period: {}. Use: day, week, or month", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for BudgetPeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BudgetPeriod::Day => write!(f, "day"),
+            BudgetPeriod::Week => write!(f, "week"),
+            BudgetPeriod::Month => write!(f, "month"),
+        }
+    }
+}
+
+/// Which purchases a budget counts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetScope {
+    All,
+    Reward(u32),
+    Tier(RewardTier),
+}
+
+impl BudgetScope {
+    fn matches(&self, reward_id: u32, tier: RewardTier) -> bool {
+        match self {
+            BudgetScope::All => true,
+            BudgetScope::Reward(id) => *id == reward_id,
+            BudgetScope::Tier(t) => *t == tier,
+        }
+    }
+}
+
+/// Cap on purchases within the period
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetLimit {
+    Count(u32),
+    Gold(u32),
+}
+
+/// A spending rule such as "at most 500 gold a week on Epic rewards"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Budget {
+    pub scope: BudgetScope,
+    pub period: BudgetPeriod,
+    pub limit: BudgetLimit,
+}
+
+impl Budget {
+    /// Purchases counted against this budget in the window ending at `now`
+    fn used<'a>(&self, purchases: &'a [PurchaseRecord], now: DateTime<Utc>) -> impl Iterator<Item = &'a PurchaseRecord> + 'a {
+        let since = now - self.period.duration();
+        let scope = self.scope.clone();
+        purchases.iter()
+            .filter(move |p| scope.matches(p.reward_id, p.tier))
+            .filter(move |p| p.purchased_at().is_some_and(|t| t > since))
+    }
+
+    /// Check a purchase against this budget, returning why it is refused
+    pub fn check(&self, reward: &Reward, cost: u32, purchases: &[PurchaseRecord], now: DateTime<Utc>) -> Option<String> {
+        if !self.scope.matches(reward.id, reward.tier) {
+            return None;
+        }
+
+        match self.limit {
+            BudgetLimit::Count(max) => {
+                let count = self.used(purchases, now).count() as u32;
+                (count + 1 > max).then(|| format!(
+                    "Budget reached: {} ({} already bought)", self.describe(std::slice::from_ref(reward)), count
+                ))
+            }
+            BudgetLimit::Gold(max) => {
+                let spent: u32 = self.used(purchases, now).map(|p| p.cost).sum();
+                (spent + cost > max).then(|| format!(
+                    "Budget reached: {} ({} already spent, this costs {})",
+                    self.describe(std::slice::from_ref(reward)), spent, cost
+                ))
+            }
+        }
+    }
+
+    /// Remaining allowance in the window ending at `now`
+    pub fn remaining(&self, purchases: &[PurchaseRecord], now: DateTime<Utc>) -> u32 {
+        match self.limit {
+            BudgetLimit::Count(max) => max.saturating_sub(self.used(purchases, now).count() as u32),
+            BudgetLimit::Gold(max) => max.saturating_sub(self.used(purchases, now).map(|p| p.cost).sum()),
+        }
+    }
+
+    /// e.g. "at most 2 purchases of Gaming Session per day"
+    pub fn describe(&self, rewards: &[Reward]) -> String {
+        let scope = match &self.scope {
+            BudgetScope::All => "all rewards".to_string(),
+            BudgetScope::Reward(id) => rewards.iter()
+                .find(|r| r.id == *id)
+                .map(|r| r.name.clone())
+                .unwrap_or_else(|| format!("reward {}", id)),
+            BudgetScope::Tier(tier) => format!("{} rewards", tier.name()),
+        };
+        match self.limit {
+            BudgetLimit::Count(max) => format!("at most {} purchase{} of {} per {}",
+                max, if max == 1 { "" } else { "s" }, scope, self.period),
+            BudgetLimit::Gold(max) => format!("at most {} gold on {} per {}", max, scope, self.period),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn purchase(reward_id: u32, tier: RewardTier, cost: u32, hours_ago: i64, now: DateTime<Utc>) -> PurchaseRecord {
+        PurchaseRecord {
+            reward_id,
+            reward_name: "Test".to_string(),
+            tier,
+            cost,
+            purchased_at: (now - Duration::hours(hours_ago)).to_rfc3339(),
+            gold_after: 0,
+        }
+    }
+
+    fn reward(id: u32, tier: RewardTier) -> Reward {
+        Reward {
+            id,
+            name: "Test".to_string(),
+            description: String::new(),
+            cost: 100,
+            tier,
+            cooldown_hours: 0,
+            last_purchased: None,
+            fixed_price: false,
+            hidden: false,
+        }
+    }
+
+    #[test]
+    fn test_count_budget() {
+        let now = Utc::now();
+        let budget = Budget { scope: BudgetScope::Reward(2), period: BudgetPeriod::Day, limit: BudgetLimit::Count(2) };
+        let mut purchases = vec![purchase(2, RewardTier::Normal, 100, 1, now)];
+
+        assert!(budget.check(&reward(2, RewardTier::Normal), 100, &purchases, now).is_none());
+        purchases.push(purchase(2, RewardTier::Normal, 100, 2, now));
+        assert!(budget.check(&reward(2, RewardTier::Normal), 100, &purchases, now).is_some());
+
+        // Other rewards and older purchases don't count
+        assert!(budget.check(&reward(3, RewardTier::Normal), 100, &purchases, now).is_none());
+        assert!(budget.check(&reward(2, RewardTier::Normal), 100, &purchases, now + Duration::hours(23)).is_none());
+    }
+
+    #[test]
+    fn test_gold_budget_by_tier() {
+        let now = Utc::now();
+        let budget = Budget { scope: BudgetScope::Tier(RewardTier::Epic), period: BudgetPeriod::Week, limit: BudgetLimit::Gold(500) };
+        let purchases = vec![
+            purchase(7, RewardTier::Epic, 400, 48, now),
+            purchase(1, RewardTier::Normal, 1000, 1, now),
+        ];
+
+        assert_eq!(budget.remaining(&purchases, now), 100);
+        assert!(budget.check(&reward(8, RewardTier::Epic), 100, &purchases, now).is_none());
+        assert!(budget.check(&reward(8, RewardTier::Epic), 101, &purchases, now).is_some());
+    }
+}
@@ -0,0 +1,213 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::progression::RewardTier;
+use super::rewards::{Reward, RewardStore};
+
+/// A shareable reward definition (no ids or purchase state)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatalogEntry {
+    pub name: String,
+    pub description: String,
+    pub cost: u32,
+    pub tier: RewardTier,
+    #[serde(default)]
+    pub cooldown_hours: u32,
+    #[serde(default)]
+    pub fixed_price: bool,
+}
+
+impl From<&Reward> for CatalogEntry {
+    fn from(reward: &Reward) -> Self {
+        Self {
+            name: reward.name.clone(),
+            description: reward.description.clone(),
+            cost: reward.cost,
+            tier: reward.tier,
+            cooldown_hours: reward.cooldown_hours,
+            fixed_price: reward.fixed_price,
+        }
+    }
+}
+
+/// A reward catalog file, in JSON or TOML (`[[rewards]]` tables)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Catalog {
+    #[serde(default)]
+    pub rewards: Vec<CatalogEntry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatalogFormat {
+    Json,
+    Toml,
+}
+
+impl CatalogFormat {
+    /// Format implied by a file extension (JSON unless it ends in `.toml`)
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => CatalogFormat::Toml,
+            _ => CatalogFormat::Json,
+        }
+    }
+}
+
+impl std::str::FromStr for CatalogFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(CatalogFormat::Json),
+            "toml" => Ok(CatalogFormat::Toml),
+            _ => Err(anyhow::anyhow!("Invalid format: {}. Use: json or toml", s)),
+        }
+    }
+}
+
+/// What an import changed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub added: u32,
+    pub updated: u32,
+    pub hidden: u32,
+}
+
+impl Catalog {
+    /// Catalog of the rewards currently offered (hidden ones are left out)
+    pub fn from_store(store: &RewardStore) -> Self {
+        Self {
+            rewards: store.rewards.iter()
+                .filter(|r| !r.hidden)
+                .map(CatalogEntry::from)
+                .collect(),
+        }
+    }
+
+    pub fn parse(text: &str, format: CatalogFormat) -> Result<Self> {
+        let catalog: Catalog = match format {
+            CatalogFormat::Json => serde_json::from_str(text)
+                .context("Failed to parse JSON catalog")?,
+            CatalogFormat::Toml => toml::from_str(text)
+                .context("Failed to parse TOML catalog")?,
+        };
+
+        for entry in &catalog.rewards {
+            if entry.name.trim().is_empty() {
+                anyhow::bail!("Catalog contains a reward without a name");
+            }
+        }
+        Ok(catalog)
+    }
+
+    pub fn render(&self, format: CatalogFormat) -> Result<String> {
+        match format {
+            CatalogFormat::Json => serde_json::to_string_pretty(self)
+                .context("Failed to serialize catalog"),
+            CatalogFormat::Toml => toml::to_string_pretty(self)
+                .context("Failed to serialize catalog"),
+        }
+    }
+}
+
+impl RewardStore {
+    /// Merge a catalog into the shop, matching rewards by name.
+    ///
+    /// Matching rewards are updated in place (keeping their id, cooldown state
+    /// and purchase history); new ones are added. With `replace`, rewards not
+    /// in the catalog are hidden rather than deleted.
+    pub fn import_catalog(&mut self, catalog: &Catalog, replace: bool) -> ImportSummary {
+        let mut summary = ImportSummary::default();
+
+        for entry in &catalog.rewards {
+            let existing = self.rewards.iter_mut()
+                .find(|r| r.name.eq_ignore_ascii_case(&entry.name));
+
+            match existing {
+                Some(reward) => {
+                    if CatalogEntry::from(&*reward) != *entry || reward.hidden {
+                        reward.name = entry.name.clone();
+                        reward.description = entry.description.clone();
+                        reward.cost = entry.cost;
+                        reward.tier = entry.tier;
+                        reward.cooldown_hours = entry.cooldown_hours;
+                        reward.fixed_price = entry.fixed_price;
+                        reward.hidden = false;
+                        summary.updated += 1;
+                    }
+                }
+                None => {
+                    let id = self.add_reward(
+                        entry.name.clone(),
+                        entry.cost,
+                        entry.description.clone(),
+                        entry.tier,
+                        entry.cooldown_hours,
+                    );
+                    if let Some(reward) = self.rewards.iter_mut().find(|r| r.id == id) {
+                        reward.fixed_price = entry.fixed_price;
+                    }
+                    summary.added += 1;
+                }
+            }
+        }
+
+        if replace {
+            for reward in &mut self.rewards {
+                let in_catalog = catalog.rewards.iter()
+                    .any(|e| e.name.eq_ignore_ascii_case(&reward.name));
+                if !in_catalog && !reward.hidden {
+                    reward.hidden = true;
+                    summary.hidden += 1;
+                }
+            }
+        }
+
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_both_formats() {
+        let store = RewardStore::new();
+        let catalog = Catalog::from_store(&store);
+
+        for format in [CatalogFormat::Json, CatalogFormat::Toml] {
+            let text = catalog.render(format).unwrap();
+            let parsed = Catalog::parse(&text, format).unwrap();
+            assert_eq!(parsed.rewards, catalog.rewards);
+        }
+    }
+
+    #[test]
+    fn test_import_merges_by_name() {
+        let mut store = RewardStore::new();
+        let catalog = Catalog::parse(r#"
+            [[rewards]]
+            name = "coffee break"
+            description = "Fancy coffee"
+            cost = 80
+            tier = "Normal"
+
+            [[rewards]]
+            name = "Board Game Night"
+            description = "Team board games"
+            cost = 400
+            tier = "Epic"
+            cooldown_hours = 168
+        "#, CatalogFormat::Toml).unwrap();
+
+        let summary = store.import_catalog(&catalog, true);
+        assert_eq!(summary, ImportSummary { added: 1, updated: 1, hidden: 9 });
+
+        let coffee = store.get_reward(1).unwrap();
+        assert_eq!(coffee.cost, 80);
+        assert_eq!(store.available_rewards().len(), 2);
+        assert_eq!(store.get_reward_by_name("Board Game Night").unwrap().id, 11);
+    }
+}
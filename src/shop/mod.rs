@@ -1,3 +1,10 @@
 pub mod rewards;
+pub mod pricing;
+pub mod budgets;
+pub mod catalog;
+pub mod savings;
 
-pub use rewards::RewardStore;
+pub use rewards::{RewardEdit, RewardStore};
+pub use pricing::{PriceContext, PricingMode};
+pub use budgets::{Budget, BudgetLimit, BudgetPeriod, BudgetScope};
+pub use savings::{SavingsGoal, SavingsRule};
@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+
+/// What dynamic prices follow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PricingMode {
+    /// Prices are always the listed cost
+    #[default]
+    Fixed,
+    /// Prices rise with character level
+    Level,
+    /// Prices follow gold earned over the recent window
+    Income,
+}
+
+impl std::str::FromStr for PricingMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "fixed" | "off" => Ok(PricingMode::Fixed),
+            "level" => Ok(PricingMode::Level),
+            "income" => Ok(PricingMode::Income),
+            _ => Err(anyhow::anyhow!("Invalid pricing mode: {}. Use: fixed, level, or income", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for PricingMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PricingMode::Fixed => write!(f, "fixed"),
+            PricingMode::Level => write!(f, "level"),
+            PricingMode::Income => write!(f, "income"),
+        }
+    }
+}
+
+/// Dynamic pricing settings, stored with the shop
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DynamicPricing {
+    pub mode: PricingMode,
+    /// Level mode: price increase per level above 1 (0.02 = +2%)
+    pub per_level: f64,
+    /// Income mode: gold per window at which rewards cost their listed price
+    pub reference_income: u32,
+    /// Income mode: days of completions counted as recent income
+    pub income_window_days: u32,
+    /// Lowest multiplier applied to a listed cost
+    pub floor: f64,
+    /// Highest multiplier applied to a listed cost
+    pub ceiling: f64,
+}
+
+impl Default for DynamicPricing {
+    fn default() -> Self {
+        Self {
+            mode: PricingMode::Fixed,
+            per_level: 0.02,
+            reference_income: 500,
+            income_window_days: 7,
+            floor: 0.5,
+            ceiling: 3.0,
+        }
+    }
+}
+
+/// Character state that prices depend on
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PriceContext {
+    pub level: u32,
+    /// Gold earned over the income window
+    pub recent_income: u32,
+    /// Fractional discount (e.g. 0.1 = 10% off from CHA)
+    pub discount: f64,
+}
+
+impl DynamicPricing {
+    /// Multiplier applied to listed costs, clamped to the floor and ceiling
+    pub fn multiplier(&self, ctx: &PriceContext) -> f64 {
+        let raw = match self.mode {
+            PricingMode::Fixed => return 1.0,
+            PricingMode::Level => 1.0 + self.per_level * ctx.level.saturating_sub(1) as f64,
+            PricingMode::Income => {
+                if self.reference_income == 0 {
+                    1.0
+                } else {
+                    ctx.recent_income as f64 / self.reference_income as f64
+                }
+            }
+        };
+
+        let floor = self.floor.max(0.0);
+        raw.clamp(floor, self.ceiling.max(floor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_mode() {
+        let pricing = DynamicPricing::default();
+        let ctx = PriceContext { level: 40, recent_income: 5000, discount: 0.0 };
+        assert_eq!(pricing.multiplier(&ctx), 1.0);
+    }
+
+    #[test]
+    fn test_level_mode_clamped() {
+        let pricing = DynamicPricing { mode: PricingMode::Level, ..Default::default() };
+        let at = |level| pricing.multiplier(&PriceContext { level, ..Default::default() });
+
+        assert_eq!(at(1), 1.0);
+        assert!((at(30) - 1.58).abs() < 1e-9);
+        assert_eq!(at(500), 3.0); // ceiling
+    }
+
+    #[test]
+    fn test_income_mode_clamped() {
+        let pricing = DynamicPricing { mode: PricingMode::Income, ..Default::default() };
+        let at = |recent_income| pricing.multiplier(&PriceContext { recent_income, ..Default::default() });
+
+        assert_eq!(at(500), 1.0);
+        assert_eq!(at(750), 1.5);
+        assert_eq!(at(0), 0.5); // floor
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 use crate::progression::RewardTier;
+use super::budgets::{Budget, BudgetScope, PurchaseRecord};
+use super::pricing::{DynamicPricing, PriceContext};
 use anyhow::{Result, Context};
 use std::path::Path;
 
@@ -13,19 +15,58 @@ pub struct Reward {
     pub cooldown_hours: u32,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_purchased: Option<String>, // ISO 8601 datetime
+    /// Opt out of dynamic pricing
+    #[serde(default)]
+    pub fixed_price: bool,
+    /// Archived: kept for history but not offered in the shop
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+/// Changes to apply to a reward; `None` leaves a field as it is
+#[derive(Debug, Clone, Default)]
+pub struct RewardEdit {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub cost: Option<u32>,
+    pub tier: Option<RewardTier>,
+    pub cooldown_hours: Option<u32>,
+    pub fixed_price: Option<bool>,
+}
+
+impl Reward {
+    /// Cost after a price multiplier and a fractional discount (e.g. 0.1 = 10% off from CHA)
+    pub fn effective_cost(&self, multiplier: f64, discount: f64) -> u32 {
+        (self.cost as f64 * multiplier * (1.0 - discount.clamp(0.0, 1.0))).round() as u32
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RewardStore {
+    #[serde(default)]
+    pub schema_version: u32,
     pub rewards: Vec<Reward>,
     pub next_id: u32,
+    #[serde(default)]
+    pub pricing: DynamicPricing,
+    #[serde(default)]
+    pub purchases: Vec<PurchaseRecord>,
+    #[serde(default)]
+    pub budgets: Vec<Budget>,
 }
 
+/// Rewards 1 to 10 are the built-in defaults
+pub const DEFAULT_REWARDS: u32 = 10;
+
 impl RewardStore {
     pub fn new() -> Self {
         Self {
+            schema_version: <Self as crate::storage::Versioned>::SCHEMA_VERSION,
             rewards: Self::default_rewards(),
-            next_id: 11, // Start after the 10 default rewards
+            next_id: DEFAULT_REWARDS + 1,
+            pricing: DynamicPricing::default(),
+            purchases: Vec::new(),
+            budgets: Vec::new(),
         }
     }
 
@@ -39,6 +80,8 @@ impl RewardStore {
                 tier: RewardTier::Normal,
                 cooldown_hours: 0,
                 last_purchased: None,
+                fixed_price: false,
+                hidden: false,
             },
             Reward {
                 id: 2,
@@ -48,6 +91,8 @@ impl RewardStore {
                 tier: RewardTier::Normal,
                 cooldown_hours: 0,
                 last_purchased: None,
+                fixed_price: false,
+                hidden: false,
             },
             Reward {
                 id: 3,
@@ -57,6 +102,8 @@ impl RewardStore {
                 tier: RewardTier::Heroic,
                 cooldown_hours: 0,
                 last_purchased: None,
+                fixed_price: false,
+                hidden: false,
             },
             Reward {
                 id: 4,
@@ -66,6 +113,8 @@ impl RewardStore {
                 tier: RewardTier::Heroic,
                 cooldown_hours: 0,
                 last_purchased: None,
+                fixed_price: false,
+                hidden: false,
             },
             Reward {
                 id: 5,
@@ -75,6 +124,8 @@ impl RewardStore {
                 tier: RewardTier::Heroic,
                 cooldown_hours: 0,
                 last_purchased: None,
+                fixed_price: false,
+                hidden: false,
             },
             Reward {
                 id: 6,
@@ -84,6 +135,8 @@ impl RewardStore {
                 tier: RewardTier::Epic,
                 cooldown_hours: 168, // 1 week
                 last_purchased: None,
+                fixed_price: false,
+                hidden: false,
             },
             Reward {
                 id: 7,
@@ -93,6 +146,8 @@ impl RewardStore {
                 tier: RewardTier::Epic,
                 cooldown_hours: 0,
                 last_purchased: None,
+                fixed_price: false,
+                hidden: false,
             },
             Reward {
                 id: 8,
@@ -102,6 +157,8 @@ impl RewardStore {
                 tier: RewardTier::Epic,
                 cooldown_hours: 336, // 2 weeks
                 last_purchased: None,
+                fixed_price: false,
+                hidden: false,
             },
             Reward {
                 id: 9,
@@ -111,6 +168,8 @@ impl RewardStore {
                 tier: RewardTier::Legendary,
                 cooldown_hours: 0,
                 last_purchased: None,
+                fixed_price: false,
+                hidden: false,
             },
             Reward {
                 id: 10,
@@ -120,6 +179,8 @@ impl RewardStore {
                 tier: RewardTier::Legendary,
                 cooldown_hours: 720, // 30 days
                 last_purchased: None,
+                fixed_price: false,
+                hidden: false,
             },
         ]
     }
@@ -129,11 +190,8 @@ impl RewardStore {
             return Ok(Self::new());
         }
 
-        let file = std::fs::File::open(path)
-            .context("Failed to open shop file")?;
-        let store: RewardStore = serde_json::from_reader(file)
-            .context("Failed to parse shop JSON")?;
-        Ok(store)
+        crate::storage::load_with_backup(path)
+            .context("Failed to load shop")
     }
 
     pub fn save(&self, path: &Path) -> Result<()> {
@@ -149,6 +207,16 @@ impl RewardStore {
             .find(|r| r.name.to_lowercase() == name.to_lowercase())
     }
 
+    /// Resolve a reward ID or name to its ID
+    pub fn find_reward_id(&self, identifier: &str) -> Result<u32> {
+        let reward = match identifier.parse::<u32>() {
+            Ok(id) => self.get_reward(id),
+            Err(_) => self.get_reward_by_name(identifier),
+        };
+        reward.map(|r| r.id)
+            .with_context(|| format!("Reward '{}' not found", identifier))
+    }
+
     pub fn add_reward(&mut self, name: String, cost: u32, description: String, tier: RewardTier, cooldown_hours: u32) -> u32 {
         let id = self.next_id;
         self.next_id += 1;
@@ -161,32 +229,111 @@ impl RewardStore {
             tier,
             cooldown_hours,
             last_purchased: None,
+            fixed_price: false,
+            hidden: false,
         });
 
         id
     }
 
-    pub fn remove_reward(&mut self, id: u32) -> Result<()> {
+    /// Delete a reward and any budget tied to it. Ids are never reused.
+    /// Default rewards are meant to be hidden instead; deleting one takes `force`.
+    pub fn remove_reward(&mut self, id: u32, force: bool) -> Result<()> {
         let index = self.rewards.iter().position(|r| r.id == id)
             .context("Reward not found")?;
-
-        // Don't allow removing default rewards (1-10)
-        if id <= 10 {
-            anyhow::bail!("Cannot remove default rewards");
+        if id <= DEFAULT_REWARDS && !force {
+            anyhow::bail!(
+                "'{}' is a default reward. Hide it with 'taskquest reward hide {}', or use --force to delete it",
+                self.rewards[index].name, id
+            );
         }
 
         self.rewards.remove(index);
+        self.budgets.retain(|b| b.scope != BudgetScope::Reward(id));
         Ok(())
     }
 
-    pub fn purchase_reward(&mut self, id: u32, gold: u32) -> Result<u32> {
+    pub fn edit_reward(&mut self, id: u32, edit: RewardEdit) -> Result<()> {
+        if let Some(ref name) = edit.name {
+            if name.trim().is_empty() {
+                anyhow::bail!("Reward name cannot be empty");
+            }
+            if self.get_reward_by_name(name).is_some_and(|r| r.id != id) {
+                anyhow::bail!("Another reward is already named '{}'", name);
+            }
+        }
+
+        let reward = self.rewards.iter_mut()
+            .find(|r| r.id == id)
+            .context("Reward not found")?;
+
+        if let Some(name) = edit.name {
+            reward.name = name;
+        }
+        if let Some(description) = edit.description {
+            reward.description = description;
+        }
+        if let Some(cost) = edit.cost {
+            reward.cost = cost;
+        }
+        if let Some(tier) = edit.tier {
+            reward.tier = tier;
+        }
+        if let Some(cooldown_hours) = edit.cooldown_hours {
+            reward.cooldown_hours = cooldown_hours;
+        }
+        if let Some(fixed_price) = edit.fixed_price {
+            reward.fixed_price = fixed_price;
+        }
+        Ok(())
+    }
+
+    /// Hide (archive) or unhide a reward
+    pub fn set_hidden(&mut self, id: u32, hidden: bool) -> Result<()> {
+        self.rewards.iter_mut()
+            .find(|r| r.id == id)
+            .context("Reward not found")?
+            .hidden = hidden;
+        Ok(())
+    }
+
+    /// Price multiplier for a reward (1.0 if it opted out of dynamic pricing)
+    pub fn price_multiplier(&self, reward: &Reward, ctx: &PriceContext) -> f64 {
+        if reward.fixed_price {
+            1.0
+        } else {
+            self.pricing.multiplier(ctx)
+        }
+    }
+
+    /// What a reward costs right now
+    pub fn cost_of(&self, reward: &Reward, ctx: &PriceContext) -> u32 {
+        reward.effective_cost(self.price_multiplier(reward, ctx), ctx.discount)
+    }
+
+    /// Purchase a reward, returning the gold actually spent.
+    /// The purchase is refused if it would break any budget, and recorded otherwise.
+    pub fn purchase_reward(&mut self, id: u32, gold: u32, ctx: &PriceContext) -> Result<u32> {
+        let now = chrono::Utc::now();
+        let reward = self.get_reward(id).context("Reward not found")?;
+        if reward.hidden {
+            anyhow::bail!("'{}' is hidden. Unhide it with 'taskquest reward unhide {}'", reward.name, id);
+        }
+        let cost = self.cost_of(reward, ctx);
+
+        if let Some(reason) = self.budgets.iter()
+            .find_map(|budget| budget.check(reward, cost, &self.purchases, now))
+        {
+            anyhow::bail!(reason);
+        }
+
         let reward = self.rewards.iter_mut()
             .find(|r| r.id == id)
             .context("Reward not found")?;
 
         // Check if enough gold
-        if gold < reward.cost {
-            anyhow::bail!("Not enough gold! Need {} but have {}", reward.cost, gold);
+        if gold < cost {
+            anyhow::bail!("Not enough gold! Need {} but have {}", cost, gold);
         }
 
         // Check cooldown
@@ -212,13 +359,24 @@ impl RewardStore {
         }
 
         // Update last purchased time
-        reward.last_purchased = Some(chrono::Utc::now().to_rfc3339());
+        reward.last_purchased = Some(now.to_rfc3339());
 
-        Ok(reward.cost)
+        let record = PurchaseRecord {
+            reward_id: reward.id,
+            reward_name: reward.name.clone(),
+            tier: reward.tier,
+            cost,
+            purchased_at: now.to_rfc3339(),
+            gold_after: gold - cost,
+        };
+        self.purchases.push(record);
+
+        Ok(cost)
     }
 
     pub fn available_rewards(&self) -> Vec<&Reward> {
         self.rewards.iter()
+            .filter(|r| !r.hidden)
             .filter(|r| {
                 if let Some(ref last) = r.last_purchased {
                     if r.cooldown_hours > 0 {
@@ -261,20 +419,75 @@ mod tests {
             250,
             "A test reward".to_string(),
             RewardTier::Normal,
+            0,
         );
         assert_eq!(id, 11);
         assert_eq!(store.rewards.len(), 11);
     }
 
+    #[test]
+    fn test_edit_hide_and_remove() {
+        let mut store = RewardStore::new();
+        store.edit_reward(1, RewardEdit { cost: Some(75), cooldown_hours: Some(2), ..Default::default() }).unwrap();
+        assert_eq!(store.get_reward(1).unwrap().cost, 75);
+        assert!(store.edit_reward(1, RewardEdit { name: Some("Movie Night".to_string()), ..Default::default() }).is_err());
+
+        store.set_hidden(2, true).unwrap();
+        assert_eq!(store.available_rewards().len(), 9);
+        assert!(store.purchase_reward(2, 1000, &PriceContext::default()).is_err());
+
+        // Defaults are only removed with force; their ids are not reused
+        assert!(store.remove_reward(3, false).is_err());
+        store.remove_reward(3, true).unwrap();
+        assert_eq!(store.add_reward("New".to_string(), 1, String::new(), RewardTier::Normal, 0), 11);
+    }
+
     #[test]
     fn test_purchase_reward() {
         let mut store = RewardStore::new();
 
         // Successful purchase
-        let cost = store.purchase_reward(1, 100).unwrap();
+        let cost = store.purchase_reward(1, 100, &PriceContext::default()).unwrap();
         assert_eq!(cost, 50);
 
         // Not enough gold
-        assert!(store.purchase_reward(10, 100).is_err());
+        assert!(store.purchase_reward(10, 100, &PriceContext::default()).is_err());
+
+        // Discount lowers the price
+        let ctx = PriceContext { discount: 0.2, ..Default::default() };
+        let cost = store.purchase_reward(2, 100, &ctx).unwrap();
+        assert_eq!(cost, 80);
+    }
+
+    #[test]
+    fn test_purchase_history_and_budget() {
+        use crate::shop::budgets::{BudgetLimit, BudgetPeriod, BudgetScope};
+
+        let mut store = RewardStore::new();
+        store.budgets.push(Budget {
+            scope: BudgetScope::Reward(1),
+            period: BudgetPeriod::Day,
+            limit: BudgetLimit::Count(1),
+        });
+
+        store.purchase_reward(1, 120, &PriceContext::default()).unwrap();
+        assert_eq!(store.purchases.len(), 1);
+        assert_eq!(store.purchases[0].gold_after, 70);
+
+        assert!(store.purchase_reward(1, 70, &PriceContext::default()).is_err());
+        assert_eq!(store.purchases.len(), 1);
+    }
+
+    #[test]
+    fn test_dynamic_pricing_opt_out() {
+        use crate::shop::PricingMode;
+
+        let mut store = RewardStore::new();
+        store.pricing.mode = PricingMode::Level;
+        store.rewards[1].fixed_price = true;
+        let ctx = PriceContext { level: 26, ..Default::default() };
+
+        assert_eq!(store.cost_of(&store.rewards[0], &ctx), 75); // 50 * 1.5
+        assert_eq!(store.cost_of(&store.rewards[1], &ctx), 100);
     }
 }
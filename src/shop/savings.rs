@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+
+/// How much of each payout goes toward a goal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SavingsRule {
+    /// Percentage of every quest's gold (1-100)
+    Percent(u8),
+    /// Fixed amount per quest (less if the quest paid less)
+    Fixed(u32),
+}
+
+impl SavingsRule {
+    fn share_of(&self, payout: u32) -> u32 {
+        match self {
+            SavingsRule::Percent(percent) => payout * (*percent).min(100) as u32 / 100,
+            SavingsRule::Fixed(amount) => (*amount).min(payout),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            SavingsRule::Percent(percent) => format!("{}% of each payout", percent),
+            SavingsRule::Fixed(amount) => format!("{} gold per quest", amount),
+        }
+    }
+}
+
+/// Gold set aside for a reward. Reserved gold is part of the character's
+/// gold but can only be spent on this reward until it is released.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavingsGoal {
+    pub reward_id: u32,
+    pub reward_name: String,
+    pub target: u32,
+    pub rule: SavingsRule,
+    pub reserved: u32,
+}
+
+impl SavingsGoal {
+    pub fn remaining(&self) -> u32 {
+        self.target.saturating_sub(self.reserved)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.reserved >= self.target
+    }
+
+    /// Progress from 0.0 to 1.0
+    pub fn progress(&self) -> f64 {
+        if self.target == 0 {
+            1.0
+        } else {
+            (self.reserved as f64 / self.target as f64).min(1.0)
+        }
+    }
+
+    /// Text progress bar of the given width
+    pub fn bar(&self, width: usize) -> String {
+        let filled = (self.progress() * width as f64).round() as usize;
+        "█".repeat(filled) + &"░".repeat(width - filled)
+    }
+}
+
+/// Set aside part of a payout for each goal in order, returning the total reserved
+pub fn allocate(goals: &mut [SavingsGoal], payout: u32) -> u32 {
+    let mut left = payout;
+    for goal in goals.iter_mut() {
+        let amount = goal.rule.share_of(payout)
+            .min(goal.remaining())
+            .min(left);
+        goal.reserved += amount;
+        left -= amount;
+    }
+    payout - left
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn goal(target: u32, rule: SavingsRule) -> SavingsGoal {
+        SavingsGoal { reward_id: 10, reward_name: "Vacation".to_string(), target, rule, reserved: 0 }
+    }
+
+    #[test]
+    fn test_allocate_percent_and_fixed() {
+        let mut goals = vec![goal(1000, SavingsRule::Percent(25)), goal(1000, SavingsRule::Fixed(30))];
+
+        assert_eq!(allocate(&mut goals, 100), 55);
+        assert_eq!(goals[0].reserved, 25);
+        assert_eq!(goals[1].reserved, 30);
+
+        // A fixed amount never takes more than the payout
+        assert_eq!(allocate(&mut goals[1..], 10), 10);
+    }
+
+    #[test]
+    fn test_allocate_stops_at_target() {
+        let mut goals = vec![goal(40, SavingsRule::Percent(50))];
+        allocate(&mut goals, 60);
+        allocate(&mut goals, 60);
+        assert_eq!(goals[0].reserved, 40);
+        assert!(goals[0].is_complete());
+        assert_eq!(goals[0].bar(4), "████");
+    }
+
+    #[test]
+    fn test_goals_cannot_exceed_payout() {
+        let mut goals = vec![goal(1000, SavingsRule::Percent(80)), goal(1000, SavingsRule::Percent(80))];
+        assert_eq!(allocate(&mut goals, 100), 100);
+        assert_eq!(goals[1].reserved, 20);
+    }
+
+    #[test]
+    fn test_reserved_gold_only_spendable_on_its_reward() {
+        use crate::character::{Character, Class};
+
+        let mut character = Character::new("Test".to_string(), Class::Warrior);
+        character.savings_goals.push(goal(1000, SavingsRule::Percent(50)));
+        character.add_gold(200);
+        assert_eq!(character.reserve_for_goals(200), 100);
+
+        assert_eq!(character.reserved_gold(), 100);
+        assert_eq!(character.spendable_gold_for(1), 100);
+        assert_eq!(character.spendable_gold_for(10), 200);
+
+        assert_eq!(character.release_goal(10, Some(30)).unwrap(), 30);
+        assert_eq!(character.spendable_gold_for(1), 130);
+        assert_eq!(character.release_goal(10, None).unwrap(), 70);
+        assert!(character.savings_goals.is_empty());
+        assert!(character.release_goal(10, None).is_err());
+    }
+}
@@ -0,0 +1,275 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Files captured by a snapshot and restored together
+pub const SNAPSHOT_FILES: [&str; 4] = ["character.json", "achievements.json", "shop.json", "history.json"];
+
+const BACKUPS_DIR: &str = "backups";
+const MANIFEST: &str = "snapshot.json";
+
+/// Snapshot settings (the `backup` section of `config.json`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupConfig {
+    /// Snapshots to keep (0 = no limit)
+    pub keep: usize,
+    /// Delete snapshots older than this many days (0 = never)
+    pub max_age_days: u32,
+    /// Minimum hours between automatic snapshots (0 = disabled)
+    pub auto_interval_hours: u32,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            keep: 10,
+            max_age_days: 30,
+            auto_interval_hours: 24,
+        }
+    }
+}
+
+/// A snapshot of the data files, stored in `backups/<id>/`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub id: String,
+    pub created_at: String, // ISO 8601 datetime
+    pub reason: String,
+    pub files: Vec<String>,
+}
+
+impl Snapshot {
+    pub fn created_at(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.created_at)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc))
+    }
+
+    pub fn dir(&self, data_dir: &Path) -> PathBuf {
+        data_dir.join(BACKUPS_DIR).join(&self.id)
+    }
+}
+
+pub struct Backups;
+
+impl Backups {
+    /// All snapshots, newest first
+    pub fn list(data_dir: &Path) -> Result<Vec<Snapshot>> {
+        let backups_dir = data_dir.join(BACKUPS_DIR);
+        if !backups_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut snapshots: Vec<Snapshot> = fs::read_dir(&backups_dir)
+            .context("Failed to read backups directory")?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let file = fs::File::open(entry.path().join(MANIFEST)).ok()?;
+                serde_json::from_reader(file).ok()
+            })
+            .collect();
+        snapshots.sort_by(|a, b| b.id.cmp(&a.id));
+
+        Ok(snapshots)
+    }
+
+    pub fn find(data_dir: &Path, id: &str) -> Result<Snapshot> {
+        Self::list(data_dir)?
+            .into_iter()
+            .find(|s| s.id == id)
+            .with_context(|| format!("Snapshot '{}' not found. See 'taskquest backup list'", id))
+    }
+
+    /// Copy the current data files into a new snapshot, then rotate old ones
+    pub fn create(data_dir: &Path, reason: &str, config: &BackupConfig) -> Result<Snapshot> {
+        let snapshot = Self::write_snapshot(data_dir, reason)?;
+        Self::rotate(data_dir, config, Utc::now())?;
+        Ok(snapshot)
+    }
+
+    fn write_snapshot(data_dir: &Path, reason: &str) -> Result<Snapshot> {
+        let now = Utc::now();
+        let backups_dir = data_dir.join(BACKUPS_DIR);
+
+        // Ids sort chronologically; add a suffix if two land in the same second
+        let base_id = now.format("%Y%m%d-%H%M%S").to_string();
+        let mut id = base_id.clone();
+        let mut n = 1;
+        while backups_dir.join(&id).exists() {
+            n += 1;
+            id = format!("{}-{}", base_id, n);
+        }
+
+        let dir = backups_dir.join(&id);
+        fs::create_dir_all(&dir)
+            .context("Failed to create snapshot directory")?;
+
+        let mut files = Vec::new();
+        for name in SNAPSHOT_FILES {
+            let source = data_dir.join(name);
+            if source.exists() {
+                fs::copy(&source, dir.join(name))
+                    .with_context(|| format!("Failed to copy {} into snapshot", name))?;
+                files.push(name.to_string());
+            }
+        }
+
+        let snapshot = Snapshot {
+            id,
+            created_at: now.to_rfc3339(),
+            reason: reason.to_string(),
+            files,
+        };
+        super::safe_write(&dir.join(MANIFEST), &snapshot)?;
+
+        Ok(snapshot)
+    }
+
+    /// Take a snapshot unless a recent one exists
+    pub fn auto_snapshot(data_dir: &Path, config: &BackupConfig) -> Result<Option<Snapshot>> {
+        if config.auto_interval_hours == 0 || !data_dir.join("character.json").exists() {
+            return Ok(None);
+        }
+
+        let cutoff = Utc::now() - Duration::hours(config.auto_interval_hours as i64);
+        let recent = Self::list(data_dir)?
+            .first()
+            .and_then(|s| s.created_at())
+            .is_some_and(|created| created > cutoff);
+        if recent {
+            return Ok(None);
+        }
+
+        Self::create(data_dir, "auto", config).map(Some)
+    }
+
+    /// Delete snapshots beyond `keep` or older than `max_age_days`.
+    /// The newest snapshot is always kept.
+    pub fn rotate(data_dir: &Path, config: &BackupConfig, now: DateTime<Utc>) -> Result<Vec<String>> {
+        let cutoff = now - Duration::days(config.max_age_days as i64);
+        let mut removed = Vec::new();
+
+        for (i, snapshot) in Self::list(data_dir)?.iter().enumerate().skip(1) {
+            let too_many = config.keep > 0 && i >= config.keep;
+            let too_old = config.max_age_days > 0
+                && snapshot.created_at().is_some_and(|created| created < cutoff);
+
+            if too_many || too_old {
+                fs::remove_dir_all(snapshot.dir(data_dir))
+                    .with_context(|| format!("Failed to delete snapshot {}", snapshot.id))?;
+                removed.push(snapshot.id.clone());
+            }
+        }
+
+        Ok(removed)
+    }
+
+    /// Replace every data file with the snapshot's copy.
+    ///
+    /// The current state is snapshotted first. Files are staged next to their
+    /// targets and renamed into place only once all of them are copied, and a
+    /// file the snapshot does not contain is removed, so the character,
+    /// achievements and shop always come from the same point in time.
+    pub fn restore(data_dir: &Path, id: &str, config: &BackupConfig) -> Result<Snapshot> {
+        let snapshot = Self::find(data_dir, id)?;
+        let dir = snapshot.dir(data_dir);
+        if !snapshot.files.iter().any(|f| f == "character.json") {
+            anyhow::bail!("Snapshot '{}' has no character data", id);
+        }
+
+        // Rotate only afterwards so the snapshot being restored can't be pruned
+        Self::write_snapshot(data_dir, &format!("before restoring {}", id))?;
+
+        let mut staged = Vec::new();
+        for name in &snapshot.files {
+            let staging = data_dir.join(name).with_extension("restore");
+            fs::copy(dir.join(name), &staging)
+                .with_context(|| format!("Failed to stage {} from snapshot", name))?;
+            staged.push((staging, data_dir.join(name)));
+        }
+
+        for (staging, target) in staged {
+            fs::rename(&staging, &target)
+                .with_context(|| format!("Failed to restore {}", target.display()))?;
+        }
+        for name in SNAPSHOT_FILES {
+            let target = data_dir.join(name);
+            if !snapshot.files.iter().any(|f| f == name) && target.exists() {
+                fs::remove_file(&target)
+                    .with_context(|| format!("Failed to remove {}", target.display()))?;
+            }
+        }
+
+        Self::rotate(data_dir, config, Utc::now())?;
+        Ok(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write(dir: &Path, name: &str, contents: &str) {
+        fs::write(dir.join(name), contents).unwrap();
+    }
+
+    #[test]
+    fn test_create_and_restore() {
+        let dir = tempdir().unwrap();
+        let data = dir.path();
+        let config = BackupConfig::default();
+
+        write(data, "character.json", "{\"v\": 1}");
+        write(data, "shop.json", "{\"v\": 1}");
+        let snapshot = Backups::create(data, "manual", &config).unwrap();
+        assert_eq!(snapshot.files, vec!["character.json", "shop.json"]);
+
+        write(data, "character.json", "{\"v\": 2}");
+        write(data, "achievements.json", "{\"v\": 2}");
+        Backups::restore(data, &snapshot.id, &config).unwrap();
+
+        assert_eq!(fs::read_to_string(data.join("character.json")).unwrap(), "{\"v\": 1}");
+        assert!(!data.join("achievements.json").exists());
+
+        // The state before the restore was kept
+        let snapshots = Backups::list(data).unwrap();
+        assert_eq!(snapshots.len(), 2);
+        assert!(snapshots[0].reason.starts_with("before restoring"));
+        assert!(snapshots[0].files.contains(&"achievements.json".to_string()));
+    }
+
+    #[test]
+    fn test_rotation_keeps_newest() {
+        let dir = tempdir().unwrap();
+        let data = dir.path();
+        write(data, "character.json", "{}");
+
+        let config = BackupConfig { keep: 2, ..Default::default() };
+        for _ in 0..4 {
+            Backups::create(data, "manual", &config).unwrap();
+        }
+        assert_eq!(Backups::list(data).unwrap().len(), 2);
+
+        // Everything is "old" a year from now, but the newest survives
+        let config = BackupConfig { keep: 0, max_age_days: 30, ..Default::default() };
+        let removed = Backups::rotate(data, &config, Utc::now() + Duration::days(365)).unwrap();
+        assert_eq!(removed.len(), 1);
+        assert_eq!(Backups::list(data).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_auto_snapshot_interval() {
+        let dir = tempdir().unwrap();
+        let data = dir.path();
+        let config = BackupConfig::default();
+
+        assert!(Backups::auto_snapshot(data, &config).unwrap().is_none()); // no character yet
+        write(data, "character.json", "{}");
+        assert!(Backups::auto_snapshot(data, &config).unwrap().is_some());
+        assert!(Backups::auto_snapshot(data, &config).unwrap().is_none());
+    }
+}
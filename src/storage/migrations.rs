@@ -0,0 +1,385 @@
+//! Schema versions and migrations for the data files.
+//!
+//! Every data file carries a `schema_version`. Files written before versioning
+//! existed count as version 0. `MIGRATIONS[n]` upgrades a file from version `n`
+//! to `n + 1`, so a type's current version is the number of its migrations.
+//! To change a file format, append a migration and never edit old ones.
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Map, Value};
+use std::path::{Path, PathBuf};
+
+use crate::achievements::AchievementTracker;
+use crate::character::Character;
+use crate::history::CompletionHistory;
+use crate::shop::RewardStore;
+
+/// Upgrades a JSON document by one schema version
+pub type Migration = fn(&mut Map<String, Value>) -> Result<()>;
+
+/// A data file type with a schema version and migration history
+pub trait Versioned: DeserializeOwned {
+    /// Migrations in order; `MIGRATIONS[n]` upgrades version `n` to `n + 1`
+    const MIGRATIONS: &'static [Migration];
+
+    /// Version written by this build
+    const SCHEMA_VERSION: u32 = Self::MIGRATIONS.len() as u32;
+}
+
+impl Versioned for Character {
+    const MIGRATIONS: &'static [Migration] = &[character_v1, character_v2, character_v3];
+}
+
+impl Versioned for AchievementTracker {
+    const MIGRATIONS: &'static [Migration] = &[achievements_v1, achievements_v2, achievements_v3];
+}
+
+impl Versioned for RewardStore {
+    const MIGRATIONS: &'static [Migration] = &[version_only, shop_v2, shop_v3, shop_v4];
+}
+
+impl Versioned for CompletionHistory {
+    const MIGRATIONS: &'static [Migration] = &[version_only, history_v2, history_v3, history_v4, history_v5, history_v6, history_v7];
+}
+
+/// Schema version recorded in a document (0 if unversioned)
+pub fn schema_version(value: &Value) -> u32 {
+    value.get("schema_version")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0) as u32
+}
+
+/// Bring a document up to the current schema of `T`.
+/// Returns the version it started at.
+pub fn migrate<T: Versioned>(value: &mut Value) -> Result<u32> {
+    let from = schema_version(value);
+    if from > T::SCHEMA_VERSION {
+        anyhow::bail!(
+            "Data was written by a newer TaskQuest (schema {} > {}). Please upgrade.",
+            from,
+            T::SCHEMA_VERSION
+        );
+    }
+
+    let object = value.as_object_mut()
+        .context("Expected a JSON object")?;
+
+    for (version, migration) in T::MIGRATIONS.iter().enumerate().skip(from as usize) {
+        migration(object)
+            .with_context(|| format!("Migration to schema {} failed", version + 1))?;
+        object.insert("schema_version".to_string(), json!(version as u32 + 1));
+    }
+
+    Ok(from)
+}
+
+/// Schema version of the file at `path`, when it is newer than this build
+pub fn newer_schema<T: Versioned>(path: &Path) -> Option<u32> {
+    let value: Value = super::load(path).ok()?;
+    Some(schema_version(&value)).filter(|version| *version > T::SCHEMA_VERSION)
+}
+
+/// Where the untouched copy of a file is kept before migrating it
+pub fn premigration_path(path: &Path, version: u32) -> PathBuf {
+    path.with_extension(format!("v{}.json", version))
+}
+
+/// Load a data file, migrating it if it is outdated.
+///
+/// The first time a file is migrated an untouched copy is kept next to it. The
+/// migrated data reaches disk the next time the file is saved.
+pub fn load_versioned<T: Versioned>(path: &Path) -> Result<T> {
+    let mut value: Value = super::load(path)?;
+
+    let from = migrate::<T>(&mut value)
+        .with_context(|| format!("Failed to migrate: {}", path.display()))?;
+
+    let data = serde_json::from_value(value)
+        .with_context(|| format!("Failed to parse JSON from: {}", path.display()))?;
+
+    if from < T::SCHEMA_VERSION {
+        let copy = premigration_path(path, from);
+        if !copy.exists() {
+            std::fs::copy(path, &copy)
+                .context("Failed to save pre-migration copy")?;
+        }
+    }
+
+    Ok(data)
+}
+
+/// Insert `key` with `default` unless it is already present
+fn set_default(object: &mut Map<String, Value>, key: &str, default: Value) {
+    object.entry(key.to_string()).or_insert(default);
+}
+
+/// Migration for formats whose only change is gaining `schema_version`
+fn version_only(_: &mut Map<String, Value>) -> Result<()> {
+    Ok(())
+}
+
+/// v1: prestige rank and history
+fn character_v1(object: &mut Map<String, Value>) -> Result<()> {
+    set_default(object, "prestige_rank", json!(0));
+    set_default(object, "prestige_history", json!([]));
+    Ok(())
+}
+
+/// v2: savings goals
+fn character_v2(object: &mut Map<String, Value>) -> Result<()> {
+    set_default(object, "savings_goals", json!([]));
+    Ok(())
+}
+
+/// v3: rest periods
+fn character_v3(object: &mut Map<String, Value>) -> Result<()> {
+    set_default(object, "rest_periods", json!([]));
+    Ok(())
+}
+
+/// v1: daily streak counters
+fn achievements_v1(object: &mut Map<String, Value>) -> Result<()> {
+    let progress = object.get_mut("progress")
+        .and_then(|p| p.as_object_mut())
+        .context("Missing achievement progress")?;
+    set_default(progress, "current_streak", json!(0));
+    set_default(progress, "longest_streak", json!(0));
+    Ok(())
+}
+
+/// v2: deep work records
+fn achievements_v2(object: &mut Map<String, Value>) -> Result<()> {
+    let progress = object.get_mut("progress")
+        .and_then(|p| p.as_object_mut())
+        .context("Missing achievement progress")?;
+    set_default(progress, "best_focus_minutes", json!(0));
+    set_default(progress, "longest_focus_block", json!(0));
+    set_default(progress, "deep_work_days", json!([]));
+    Ok(())
+}
+
+/// v3: focus session counters
+fn achievements_v3(object: &mut Map<String, Value>) -> Result<()> {
+    let progress = object.get_mut("progress")
+        .and_then(|p| p.as_object_mut())
+        .context("Missing achievement progress")?;
+    set_default(progress, "sessions_completed", json!(0));
+    set_default(progress, "session_minutes", json!(0));
+    set_default(progress, "session_day", Value::Null);
+    set_default(progress, "session_day_count", json!(0));
+    set_default(progress, "most_sessions_in_day", json!(0));
+    Ok(())
+}
+
+/// v2: dynamic pricing settings and per-reward opt-out
+fn shop_v2(object: &mut Map<String, Value>) -> Result<()> {
+    set_default(object, "pricing", json!({ "mode": "fixed" }));
+    if let Some(rewards) = object.get_mut("rewards").and_then(|r| r.as_array_mut()) {
+        for reward in rewards.iter_mut().filter_map(|r| r.as_object_mut()) {
+            set_default(reward, "fixed_price", json!(false));
+        }
+    }
+    Ok(())
+}
+
+/// v3: purchase history and spending budgets
+fn shop_v3(object: &mut Map<String, Value>) -> Result<()> {
+    set_default(object, "purchases", json!([]));
+    set_default(object, "budgets", json!([]));
+    Ok(())
+}
+
+/// v4: hidden (archived) rewards
+fn shop_v4(object: &mut Map<String, Value>) -> Result<()> {
+    if let Some(rewards) = object.get_mut("rewards").and_then(|r| r.as_array_mut()) {
+        for reward in rewards.iter_mut().filter_map(|r| r.as_object_mut()) {
+            set_default(reward, "hidden", json!(false));
+        }
+    }
+    Ok(())
+}
+
+/// v2: start time and calibrated challenge of each completion
+fn history_v2(object: &mut Map<String, Value>) -> Result<()> {
+    if let Some(completions) = object.get_mut("completions").and_then(|c| c.as_array_mut()) {
+        for completion in completions.iter_mut().filter_map(|c| c.as_object_mut()) {
+            set_default(completion, "started_at", Value::Null);
+            set_default(completion, "xp_challenge", Value::Null);
+        }
+    }
+    Ok(())
+}
+
+/// v3: focus time tracked in Timewarrior
+fn history_v3(object: &mut Map<String, Value>) -> Result<()> {
+    if let Some(completions) = object.get_mut("completions").and_then(|c| c.as_array_mut()) {
+        for completion in completions.iter_mut().filter_map(|c| c.as_object_mut()) {
+            set_default(completion, "focus_xp", json!(0));
+            set_default(completion, "focus_minutes", Value::Null);
+        }
+    }
+    Ok(())
+}
+
+/// v4: focus sessions
+fn history_v4(object: &mut Map<String, Value>) -> Result<()> {
+    set_default(object, "sessions", json!([]));
+    Ok(())
+}
+
+/// v5: habits (recurring tasks)
+fn history_v5(object: &mut Map<String, Value>) -> Result<()> {
+    if let Some(completions) = object.get_mut("completions").and_then(|c| c.as_array_mut()) {
+        for completion in completions.iter_mut().filter_map(|c| c.as_object_mut()) {
+            set_default(completion, "habit", Value::Null);
+            set_default(completion, "recur", Value::Null);
+            set_default(completion, "habit_xp", json!(0));
+            set_default(completion, "habit_gold", json!(0));
+        }
+    }
+    Ok(())
+}
+
+/// v6: reward reductions for farming trivial quests
+fn history_v6(object: &mut Map<String, Value>) -> Result<()> {
+    if let Some(completions) = object.get_mut("completions").and_then(|c| c.as_array_mut()) {
+        for completion in completions.iter_mut().filter_map(|c| c.as_object_mut()) {
+            set_default(completion, "trivial_multiplier", Value::Null);
+            set_default(completion, "instant_multiplier", Value::Null);
+        }
+    }
+    Ok(())
+}
+
+/// v7: itemised reward breakdowns
+fn history_v7(object: &mut Map<String, Value>) -> Result<()> {
+    if let Some(completions) = object.get_mut("completions").and_then(|c| c.as_array_mut()) {
+        for completion in completions.iter_mut().filter_map(|c| c.as_object_mut()) {
+            set_default(completion, "breakdown", Value::Null);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    /// Every historical format of every file, as (name, contents)
+    const FIXTURES: &[(&str, &str)] = &[
+        ("character_v0.json", include_str!("../../tests/fixtures/character_v0.json")),
+        ("character_v1.json", include_str!("../../tests/fixtures/character_v1.json")),
+        ("character_v2.json", include_str!("../../tests/fixtures/character_v2.json")),
+        ("character_v3.json", include_str!("../../tests/fixtures/character_v3.json")),
+        ("achievements_v0.json", include_str!("../../tests/fixtures/achievements_v0.json")),
+        ("achievements_v1.json", include_str!("../../tests/fixtures/achievements_v1.json")),
+        ("achievements_v2.json", include_str!("../../tests/fixtures/achievements_v2.json")),
+        ("achievements_v3.json", include_str!("../../tests/fixtures/achievements_v3.json")),
+        ("shop_v0.json", include_str!("../../tests/fixtures/shop_v0.json")),
+        ("shop_v1.json", include_str!("../../tests/fixtures/shop_v1.json")),
+        ("shop_v2.json", include_str!("../../tests/fixtures/shop_v2.json")),
+        ("shop_v3.json", include_str!("../../tests/fixtures/shop_v3.json")),
+        ("shop_v4.json", include_str!("../../tests/fixtures/shop_v4.json")),
+        ("history_v0.json", include_str!("../../tests/fixtures/history_v0.json")),
+        ("history_v1.json", include_str!("../../tests/fixtures/history_v1.json")),
+        ("history_v2.json", include_str!("../../tests/fixtures/history_v2.json")),
+        ("history_v3.json", include_str!("../../tests/fixtures/history_v3.json")),
+        ("history_v4.json", include_str!("../../tests/fixtures/history_v4.json")),
+        ("history_v5.json", include_str!("../../tests/fixtures/history_v5.json")),
+        ("history_v6.json", include_str!("../../tests/fixtures/history_v6.json")),
+        ("history_v7.json", include_str!("../../tests/fixtures/history_v7.json")),
+    ];
+
+    fn fixtures(prefix: &str) -> impl Iterator<Item = (&'static str, &'static str)> + '_ {
+        FIXTURES.iter().copied().filter(move |(name, _)| name.starts_with(prefix))
+    }
+
+    /// Write a fixture to a temp dir and load it the way the app does
+    fn load_fixture<T: Versioned>(name: &str, contents: &str) -> (T, tempfile::TempDir, PathBuf) {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(name.split('_').next().unwrap()).with_extension("json");
+        std::fs::write(&path, contents).unwrap();
+        let data = load_versioned::<T>(&path)
+            .unwrap_or_else(|e| panic!("{} failed to load: {:#}", name, e));
+        (data, dir, path)
+    }
+
+    fn assert_premigration_copy<T: Versioned>(path: &Path, original: &str) {
+        let from = schema_version(&serde_json::from_str(original).unwrap());
+        if from < T::SCHEMA_VERSION {
+            let copy = std::fs::read_to_string(premigration_path(path, from)).unwrap();
+            assert_eq!(copy, original);
+        }
+    }
+
+    #[test]
+    fn test_every_version_has_a_fixture() {
+        for (prefix, version) in [
+            ("character", Character::SCHEMA_VERSION),
+            ("achievements", AchievementTracker::SCHEMA_VERSION),
+            ("shop", RewardStore::SCHEMA_VERSION),
+            ("history", CompletionHistory::SCHEMA_VERSION),
+        ] {
+            for v in 0..=version {
+                let name = format!("{}_v{}.json", prefix, v);
+                assert!(FIXTURES.iter().any(|(n, _)| *n == name), "missing fixture {}", name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_load_character_fixtures() {
+        for (name, contents) in fixtures("character") {
+            let (character, _dir, path) = load_fixture::<Character>(name, contents);
+            assert_eq!(character.name, "Aria");
+            assert_eq!(character.level, 3);
+            assert_eq!(character.schema_version, Character::SCHEMA_VERSION);
+            assert_premigration_copy::<Character>(&path, contents);
+        }
+    }
+
+    #[test]
+    fn test_load_achievement_fixtures() {
+        for (name, contents) in fixtures("achievements") {
+            let (tracker, _dir, path) = load_fixture::<AchievementTracker>(name, contents);
+            assert!(tracker.unlocked.contains("first_steps"));
+            assert_eq!(tracker.progress.quests_completed, 12);
+            assert_premigration_copy::<AchievementTracker>(&path, contents);
+        }
+    }
+
+    #[test]
+    fn test_load_shop_fixtures() {
+        for (name, contents) in fixtures("shop") {
+            let (shop, _dir, path) = load_fixture::<RewardStore>(name, contents);
+            assert_eq!(shop.next_id, 12);
+            assert!(shop.get_reward(11).is_some());
+            assert_premigration_copy::<RewardStore>(&path, contents);
+        }
+    }
+
+    #[test]
+    fn test_load_history_fixtures() {
+        for (name, contents) in fixtures("history") {
+            let (history, _dir, path) = load_fixture::<CompletionHistory>(name, contents);
+            assert_eq!(history.completions.len(), 1);
+            assert_premigration_copy::<CompletionHistory>(&path, contents);
+        }
+    }
+
+    #[test]
+    fn test_newer_schema_is_rejected() {
+        let mut value = json!({ "schema_version": Character::SCHEMA_VERSION + 1 });
+        assert!(migrate::<Character>(&mut value).is_err());
+
+        // The older backup must not silently replace the newer data
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("character.json");
+        std::fs::write(&path, value.to_string()).unwrap();
+        std::fs::write(path.with_extension("bak"), fixtures("character").last().unwrap().1).unwrap();
+        let error = crate::storage::load_with_backup::<Character>(&path).unwrap_err();
+        assert!(error.to_string().contains("Upgrade TaskQuest"), "{}", error);
+    }
+}
@@ -5,18 +5,27 @@ use std::io::Write;
 use std::path::{Path, PathBuf};
 
 pub mod git_sync;
+pub mod profiles;
+pub mod migrations;
+pub mod backups;
+
+pub use migrations::Versioned;
 
-#[allow(unused_imports)]
 pub use git_sync::GitSync;
 
-/// Get the TaskQuest data directory
+/// Get the TaskQuest data directory for the active profile
 pub fn get_data_dir() -> Result<PathBuf> {
-    let data_dir = if let Ok(custom_dir) = std::env::var("TASKQUEST_DATA") {
-        PathBuf::from(custom_dir)
-    } else {
-        let home = std::env::var("HOME").context("HOME environment variable not set")?;
-        PathBuf::from(home).join(".taskquest")
-    };
+    let base_dir = profiles::base_dir()?;
+    let profile = profiles::active_profile(&base_dir)?;
+
+    if !profiles::exists(&base_dir, &profile)? {
+        anyhow::bail!(
+            "Profile '{}' does not exist. Create it with 'taskquest profile create {}'",
+            profile,
+            profile
+        );
+    }
+    let data_dir = profiles::profile_dir(&base_dir, &profile);
 
     if !data_dir.exists() {
         fs::create_dir_all(&data_dir)
@@ -68,15 +77,26 @@ pub fn load<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T> {
     Ok(data)
 }
 
-/// Try to load data, falling back to backup if main file is corrupted
-pub fn load_with_backup<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T> {
-    match load(path) {
+/// Try to load data, falling back to backup if main file is corrupted.
+/// Outdated files are migrated to the current schema on the way in.
+pub fn load_with_backup<T: Versioned>(path: &Path) -> Result<T> {
+    match migrations::load_versioned(path) {
         Ok(data) => Ok(data),
         Err(e) => {
+            // Data from a newer TaskQuest isn't corrupt, and the older backup would lose it
+            if let Some(version) = migrations::newer_schema::<T>(path) {
+                anyhow::bail!(
+                    "{} was written by a newer TaskQuest (schema {} > {}). Upgrade TaskQuest to open it",
+                    path.display(),
+                    version,
+                    T::SCHEMA_VERSION
+                );
+            }
+
             let bak_path = path.with_extension("bak");
             if bak_path.exists() {
                 eprintln!("Warning: Main file corrupted, loading from backup");
-                load(&bak_path).context("Failed to load from backup")
+                migrations::load_versioned(&bak_path).context("Failed to load from backup")
             } else {
                 Err(e)
             }
@@ -0,0 +1,255 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Name of the profile stored directly in the base data directory
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Profile chosen for this process (via `--profile` or a hook rule)
+static SELECTED_PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Pick a profile to use for the rest of this process.
+/// Returns false if a profile was already selected.
+pub fn select(name: &str) -> Result<bool> {
+    validate_name(name)?;
+    Ok(SELECTED_PROFILE.set(name.to_string()).is_ok())
+}
+
+/// Rule mapping tasks to a profile, checked by the hooks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileRule {
+    pub profile: String,
+    /// Matches tasks whose project starts with this prefix
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_prefix: Option<String>,
+    /// Matches hooks run against this Taskwarrior data location
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_data: Option<String>,
+}
+
+impl ProfileRule {
+    /// A rule matches when every condition it sets matches
+    pub fn matches(&self, project: Option<&str>, task_data: Option<&str>) -> bool {
+        if self.project_prefix.is_none() && self.task_data.is_none() {
+            return false;
+        }
+
+        let project_ok = match &self.project_prefix {
+            Some(prefix) => project.is_some_and(|p| p.starts_with(prefix.as_str())),
+            None => true,
+        };
+        let data_ok = match &self.task_data {
+            Some(location) => task_data.is_some_and(|d| {
+                Path::new(d.trim_end_matches('/')) == Path::new(location.trim_end_matches('/'))
+            }),
+            None => true,
+        };
+
+        project_ok && data_ok
+    }
+}
+
+/// Profile settings stored in `profiles.json` in the base data directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfileConfig {
+    #[serde(default)]
+    pub default: Option<String>,
+    #[serde(default)]
+    pub rules: Vec<ProfileRule>,
+}
+
+impl ProfileConfig {
+    pub fn load(base_dir: &Path) -> Result<Self> {
+        let path = base_dir.join("profiles.json");
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let file = fs::File::open(&path)
+            .context("Failed to open profiles file")?;
+        let config: ProfileConfig = serde_json::from_reader(file)
+            .context("Failed to parse profiles JSON")?;
+        Ok(config)
+    }
+
+    pub fn save(&self, base_dir: &Path) -> Result<()> {
+        super::safe_write(&base_dir.join("profiles.json"), self)
+    }
+
+    /// First profile whose rule matches the task, if any
+    pub fn profile_for_task(&self, project: Option<&str>, task_data: Option<&str>) -> Option<&str> {
+        self.rules.iter()
+            .find(|rule| rule.matches(project, task_data))
+            .map(|rule| rule.profile.as_str())
+    }
+}
+
+/// Base TaskQuest directory (`TASKQUEST_DATA` or `~/.taskquest`)
+pub fn base_dir() -> Result<PathBuf> {
+    if let Ok(custom_dir) = std::env::var("TASKQUEST_DATA") {
+        return Ok(PathBuf::from(custom_dir));
+    }
+
+    let home = std::env::var("HOME").context("HOME environment variable not set")?;
+    Ok(PathBuf::from(home).join(".taskquest"))
+}
+
+/// Data directory for a profile. The default profile lives in the base
+/// directory so existing single-profile setups keep working. `name` must
+/// have passed [`validate_name`].
+pub fn profile_dir(base_dir: &Path, name: &str) -> PathBuf {
+    if name == DEFAULT_PROFILE {
+        base_dir.to_path_buf()
+    } else {
+        base_dir.join("profiles").join(name)
+    }
+}
+
+/// Profile in effect: `--profile`, then `TASKQUEST_PROFILE`, then the configured default
+pub fn active_profile(base_dir: &Path) -> Result<String> {
+    if let Some(name) = SELECTED_PROFILE.get() {
+        return Ok(name.clone());
+    }
+
+    let name = match std::env::var("TASKQUEST_PROFILE") {
+        Ok(name) => name,
+        Err(_) => ProfileConfig::load(base_dir)?
+            .default
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_string()),
+    };
+    validate_name(&name)?;
+    Ok(name)
+}
+
+/// All profiles, starting with the default one
+pub fn list(base_dir: &Path) -> Result<Vec<String>> {
+    let mut names = vec![DEFAULT_PROFILE.to_string()];
+
+    let profiles_dir = base_dir.join("profiles");
+    if profiles_dir.exists() {
+        let mut others: Vec<String> = fs::read_dir(&profiles_dir)
+            .context("Failed to read profiles directory")?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect();
+        others.sort();
+        names.extend(others);
+    }
+
+    Ok(names)
+}
+
+pub fn exists(base_dir: &Path, name: &str) -> Result<bool> {
+    validate_name(name)?;
+    Ok(name == DEFAULT_PROFILE || profile_dir(base_dir, name).is_dir())
+}
+
+pub fn validate_name(name: &str) -> Result<()> {
+    if name.is_empty()
+        || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        anyhow::bail!("Invalid profile name '{}'. Use letters, digits, '-' and '_'", name);
+    }
+    Ok(())
+}
+
+pub fn create(base_dir: &Path, name: &str) -> Result<PathBuf> {
+    if exists(base_dir, name)? {
+        anyhow::bail!("Profile '{}' already exists", name);
+    }
+
+    let dir = profile_dir(base_dir, name);
+    fs::create_dir_all(&dir)
+        .context("Failed to create profile directory")?;
+    Ok(dir)
+}
+
+pub fn delete(base_dir: &Path, name: &str) -> Result<()> {
+    validate_name(name)?;
+    if name == DEFAULT_PROFILE {
+        anyhow::bail!("The default profile cannot be deleted");
+    }
+    if !exists(base_dir, name)? {
+        anyhow::bail!("Profile '{}' does not exist", name);
+    }
+
+    fs::remove_dir_all(profile_dir(base_dir, name))
+        .context("Failed to delete profile directory")?;
+
+    // Drop references to the deleted profile
+    let mut config = ProfileConfig::load(base_dir)?;
+    if config.default.as_deref() == Some(name) {
+        config.default = None;
+    }
+    config.rules.retain(|rule| rule.profile != name);
+    config.save(base_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::safe_write;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_profile_dirs() {
+        let dir = tempdir().unwrap();
+        let base = dir.path();
+
+        assert_eq!(profile_dir(base, DEFAULT_PROFILE), base);
+        assert_eq!(list(base).unwrap(), vec!["default"]);
+
+        create(base, "work").unwrap();
+        assert!(create(base, "work").is_err());
+        assert!(create(base, "../evil").is_err());
+        assert_eq!(list(base).unwrap(), vec!["default", "work"]);
+
+        assert!(delete(base, DEFAULT_PROFILE).is_err());
+        delete(base, "work").unwrap();
+        assert!(!exists(base, "work").unwrap());
+    }
+
+    #[test]
+    fn test_names_cannot_escape_the_data_dir() {
+        let dir = tempdir().unwrap();
+        let base = dir.path();
+        safe_write(&base.join("character.json"), &"keep me").unwrap();
+        create(base, "work").unwrap();
+
+        assert!(delete(base, "..").is_err());
+        assert!(delete(base, "../x").is_err());
+        assert!(delete(base, "../profiles/work").is_err());
+        assert!(exists(base, "..").is_err());
+        assert!(select("../../x").is_err());
+
+        assert!(base.join("character.json").exists());
+        assert!(exists(base, "work").unwrap());
+    }
+
+    #[test]
+    fn test_rules() {
+        let config = ProfileConfig {
+            default: None,
+            rules: vec![
+                ProfileRule {
+                    profile: "work".to_string(),
+                    project_prefix: Some("work".to_string()),
+                    task_data: None,
+                },
+                ProfileRule {
+                    profile: "side".to_string(),
+                    project_prefix: None,
+                    task_data: Some("/home/me/.task-side/".to_string()),
+                },
+            ],
+        };
+
+        assert_eq!(config.profile_for_task(Some("work.backend"), None), Some("work"));
+        assert_eq!(config.profile_for_task(Some("home"), Some("/home/me/.task-side")), Some("side"));
+        assert_eq!(config.profile_for_task(Some("home"), Some("/home/me/.task")), None);
+        assert_eq!(config.profile_for_task(None, None), None);
+    }
+}
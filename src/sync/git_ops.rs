@@ -3,6 +3,9 @@ use git2::{Repository, Signature, IndexAddOption, StatusOptions};
 use std::path::Path;
 use colored::Colorize;
 
+/// Local-only files kept out of the sync repository
+const GITIGNORE_PATTERNS: [&str; 4] = ["*.bak", "*.tmp", ".sync-conflict-*", "backups/"];
+
 pub struct GitSync;
 
 impl GitSync {
@@ -21,9 +24,7 @@ impl GitSync {
             .context("Failed to initialize git repository")?;
 
         // Create .gitignore
-        let gitignore_path = data_dir.join(".gitignore");
-        std::fs::write(&gitignore_path, "*.bak\n*.tmp\n.sync-conflict-*\n")
-            .context("Failed to create .gitignore")?;
+        Self::ensure_gitignore(data_dir)?;
 
         // Add all JSON files
         let mut index = repo.index()?;
@@ -66,10 +67,36 @@ impl GitSync {
         Ok(())
     }
 
+    /// Make sure local-only files (backups, temp files) are never committed.
+    /// Repositories created by older versions get the new patterns appended.
+    fn ensure_gitignore(data_dir: &Path) -> Result<()> {
+        let gitignore_path = data_dir.join(".gitignore");
+        let mut contents = std::fs::read_to_string(&gitignore_path).unwrap_or_default();
+
+        let missing: Vec<&str> = GITIGNORE_PATTERNS.iter()
+            .copied()
+            .filter(|pattern| !contents.lines().any(|line| line.trim() == *pattern))
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        if !contents.is_empty() && !contents.ends_with('\n') {
+            contents.push('\n');
+        }
+        for pattern in missing {
+            contents.push_str(pattern);
+            contents.push('\n');
+        }
+        std::fs::write(&gitignore_path, contents)
+            .context("Failed to update .gitignore")
+    }
+
     /// Commit current changes
     pub fn commit(data_dir: &Path, message: &str) -> Result<()> {
         let repo = Repository::open(data_dir)
             .context("Not a git repository. Run 'taskquest sync init' first")?;
+        Self::ensure_gitignore(data_dir)?;
 
         // Check for changes
         let mut status_opts = StatusOptions::new();
@@ -219,8 +246,9 @@ impl GitSync {
             if let Ok(commit) = head.peel_to_commit() {
                 let msg = commit.message().unwrap_or("").lines().next().unwrap_or("");
                 let time = commit.time();
-                let datetime = chrono::NaiveDateTime::from_timestamp_opt(time.seconds(), 0)
-                    .unwrap_or_default();
+                let datetime = chrono::DateTime::from_timestamp(time.seconds(), 0)
+                    .unwrap_or_default()
+                    .naive_utc();
                 println!("║ Last commit: {}                        ║", datetime.format("%Y-%m-%d %H:%M"));
                 println!("║ Message: {}                            ║", msg);
             }
@@ -255,8 +283,9 @@ impl GitSync {
             let commit = repo.find_commit(oid)?;
 
             let time = commit.time();
-            let datetime = chrono::NaiveDateTime::from_timestamp_opt(time.seconds(), 0)
-                .unwrap_or_default();
+            let datetime = chrono::DateTime::from_timestamp(time.seconds(), 0)
+                .unwrap_or_default()
+                .naive_utc();
 
             let msg = commit.message().unwrap_or("").lines().next().unwrap_or("");
             let short_id = &oid.to_string()[..7];
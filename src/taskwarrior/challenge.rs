@@ -0,0 +1,310 @@
+//! Default challenge for tasks added without one.
+//!
+//! The suggestion starts from the average challenge of similar quests already
+//! completed in the task's project (or `default` without enough history), then
+//! adds the adjustments configured for its tags, project, priority and estimate.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+use super::parser::TaskData;
+use crate::history::CompletionHistory;
+
+/// Adjustment for estimates up to a number of minutes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EstimateBand {
+    pub up_to_minutes: u32,
+    pub adjust: i8,
+}
+
+/// `challenge` section of config.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChallengeConfig {
+    /// Starting point when there isn't enough history
+    pub default: u8,
+    /// Adjustment per tag
+    pub tags: BTreeMap<String, i8>,
+    /// Adjustment per project; a project also covers its subprojects
+    pub projects: BTreeMap<String, i8>,
+    /// Adjustment per Taskwarrior priority (H/M/L)
+    pub priority: BTreeMap<String, i8>,
+    /// Adjustments by the `estimate` UDA, shortest first. Longer estimates
+    /// than the last band get the last band's adjustment.
+    pub estimate: Vec<EstimateBand>,
+    /// Completed quests in a project needed before their average is used
+    pub min_history: usize,
+}
+
+impl Default for ChallengeConfig {
+    fn default() -> Self {
+        Self {
+            default: 5,
+            tags: BTreeMap::new(),
+            projects: BTreeMap::new(),
+            priority: BTreeMap::from([("H".to_string(), 1), ("L".to_string(), -1)]),
+            estimate: vec![
+                EstimateBand { up_to_minutes: 15, adjust: -2 },
+                EstimateBand { up_to_minutes: 30, adjust: -1 },
+                EstimateBand { up_to_minutes: 120, adjust: 0 },
+                EstimateBand { up_to_minutes: 240, adjust: 1 },
+                EstimateBand { up_to_minutes: 480, adjust: 2 },
+                EstimateBand { up_to_minutes: 1440, adjust: 3 },
+            ],
+            min_history: 3,
+        }
+    }
+}
+
+/// A suggested challenge and how it was reached
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub challenge: u8,
+    /// Challenge before adjustments
+    pub base: f64,
+    /// Where the base came from
+    pub base_reason: String,
+    /// Each signal that moved the challenge, with its adjustment
+    pub adjustments: Vec<(String, i8)>,
+}
+
+/// Words that say something about a task (for matching similar ones)
+fn keywords(description: &str) -> HashSet<String> {
+    description
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= 4)
+        .map(|w| w.to_lowercase())
+        .collect()
+}
+
+/// Average challenge of completed quests in the task's project, preferring
+/// those that share a keyword with its description
+fn history_base(config: &ChallengeConfig, history: &CompletionHistory, task: &TaskData) -> Option<(f64, String)> {
+    let project = task.project.as_deref()?;
+    let in_project: Vec<_> = history.completions.iter()
+        .filter(|r| r.project.as_deref() == Some(project))
+        .collect();
+
+    let words = keywords(&task.description);
+    let similar: Vec<_> = in_project.iter()
+        .filter(|r| !keywords(&r.description).is_disjoint(&words))
+        .collect();
+
+    let min = config.min_history.max(1);
+    let (records, what) = if similar.len() >= min {
+        (similar.into_iter().copied().collect::<Vec<_>>(), "similar quests")
+    } else if in_project.len() >= min {
+        (in_project, "quests")
+    } else {
+        return None;
+    };
+
+    let average = records.iter().map(|r| r.challenge as f64).sum::<f64>() / records.len() as f64;
+    Some((average, format!("average of {} {} in {}", records.len(), what, project)))
+}
+
+/// Most specific configured project covering this one (`work` covers `work.reports`)
+fn project_adjustment(config: &ChallengeConfig, project: &str) -> Option<(String, i8)> {
+    config.projects.iter()
+        .filter(|(name, _)| {
+            project == name.as_str()
+                || project.strip_prefix(name.as_str()).is_some_and(|rest| rest.starts_with('.'))
+        })
+        .max_by_key(|(name, _)| name.len())
+        .map(|(name, adjust)| (format!("project {}", name), *adjust))
+}
+
+fn estimate_adjustment(config: &ChallengeConfig, estimate: &str) -> Option<(String, i8)> {
+    let minutes = parse_estimate(estimate)?;
+    let band = config.estimate.iter()
+        .find(|b| minutes <= b.up_to_minutes)
+        .or(config.estimate.last())?;
+    Some((format!("estimate {}", estimate), band.adjust))
+}
+
+/// Suggest a challenge for a task that doesn't have one
+pub fn suggest(config: &ChallengeConfig, history: &CompletionHistory, task: &TaskData) -> Suggestion {
+    let (base, base_reason) = history_base(config, history, task)
+        .unwrap_or_else(|| (config.default as f64, "default".to_string()));
+
+    let mut adjustments = Vec::new();
+    for tag in &task.tags {
+        if let Some(adjust) = config.tags.get(tag) {
+            adjustments.push((format!("tag +{}", tag), *adjust));
+        }
+    }
+    if let Some(adjustment) = task.project.as_deref().and_then(|p| project_adjustment(config, p)) {
+        adjustments.push(adjustment);
+    }
+    if let Some(priority) = task.priority.as_deref() {
+        if let Some(adjust) = config.priority.get(&priority.to_uppercase()) {
+            adjustments.push((format!("priority {}", priority), *adjust));
+        }
+    }
+    if let Some(adjustment) = task.estimate.as_deref().and_then(|e| estimate_adjustment(config, e)) {
+        adjustments.push(adjustment);
+    }
+    adjustments.retain(|(_, adjust)| *adjust != 0);
+
+    let total = base + adjustments.iter().map(|(_, a)| *a as f64).sum::<f64>();
+    Suggestion {
+        challenge: total.round().clamp(1.0, 10.0) as u8,
+        base,
+        base_reason,
+        adjustments,
+    }
+}
+
+/// Parse a duration into minutes. Accepts what Taskwarrior stores for a
+/// duration UDA (ISO 8601, e.g. `PT1H30M`) and shorthand like `45min` or `2h`.
+pub fn parse_estimate(value: &str) -> Option<u32> {
+    let value = value.trim().to_lowercase();
+    if let Some(iso) = value.strip_prefix('p') {
+        return parse_iso_duration(iso);
+    }
+
+    let split = value.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let minutes = match unit.trim() {
+        "s" | "sec" | "secs" | "seconds" => number / 60.0,
+        "m" | "min" | "mins" | "minutes" => number,
+        "h" | "hr" | "hrs" | "hours" => number * 60.0,
+        "d" | "day" | "days" => number * 1440.0,
+        "w" | "wk" | "wks" | "weeks" => number * 10080.0,
+        "mo" | "mos" | "month" | "months" => number * 43200.0,
+        "y" | "yr" | "yrs" | "year" | "years" => number * 525600.0,
+        _ => return None,
+    };
+    Some(minutes.round() as u32)
+}
+
+/// The part of an ISO 8601 duration after the leading `P`
+fn parse_iso_duration(iso: &str) -> Option<u32> {
+    let mut minutes = 0.0;
+    let mut number = String::new();
+    let mut in_time = false;
+
+    for c in iso.chars() {
+        match c {
+            't' => in_time = true,
+            '0'..='9' | '.' => number.push(c),
+            unit => {
+                let n: f64 = number.parse().ok()?;
+                number.clear();
+                minutes += n * match (unit, in_time) {
+                    ('y', false) => 525600.0,
+                    ('m', false) => 43200.0,
+                    ('w', false) => 10080.0,
+                    ('d', false) => 1440.0,
+                    ('h', true) => 60.0,
+                    ('m', true) => 1.0,
+                    ('s', true) => 1.0 / 60.0,
+                    _ => return None,
+                };
+            }
+        }
+    }
+
+    if !number.is_empty() {
+        return None;
+    }
+    Some(minutes.round() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::CompletionRecord;
+    use crate::progression::TaskTiming;
+
+    fn task(description: &str, project: Option<&str>) -> TaskData {
+        TaskData::from_json(&serde_json::json!({
+            "uuid": "1",
+            "status": "pending",
+            "description": description,
+            "project": project,
+        }).to_string()).unwrap()
+    }
+
+    fn record(description: &str, project: &str, challenge: u8) -> CompletionRecord {
+        CompletionRecord {
+            uuid: description.to_string(),
+            description: description.to_string(),
+            project: Some(project.to_string()),
+            completed_at: "2026-01-01T00:00:00+00:00".to_string(),
+            challenge,
+            timing: TaskTiming::OnTime,
+            level_after: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_estimate() {
+        assert_eq!(parse_estimate("PT1H30M"), Some(90));
+        assert_eq!(parse_estimate("P1DT2H"), Some(1560));
+        assert_eq!(parse_estimate("45min"), Some(45));
+        assert_eq!(parse_estimate("2h"), Some(120));
+        assert_eq!(parse_estimate("1.5 days"), Some(2160));
+        assert_eq!(parse_estimate("soon"), None);
+        assert_eq!(parse_estimate("PT5X"), None);
+    }
+
+    #[test]
+    fn test_signals_adjust_default() {
+        let mut config = ChallengeConfig::default();
+        config.tags.insert("quick".to_string(), -2);
+        config.projects.insert("work".to_string(), 1);
+        let history = CompletionHistory::new();
+
+        let plain = suggest(&config, &history, &task("Water plants", None));
+        assert_eq!(plain.challenge, 5);
+        assert!(plain.adjustments.is_empty());
+
+        let mut t = task("Quarterly report", Some("work.reports"));
+        t.priority = Some("H".to_string());
+        t.estimate = Some("PT6H".to_string());
+        let s = suggest(&config, &history, &t);
+        assert_eq!(s.adjustments, [
+            ("project work".to_string(), 1),
+            ("priority H".to_string(), 1),
+            ("estimate PT6H".to_string(), 2),
+        ]);
+        assert_eq!(s.challenge, 9);
+
+        t.tags = vec!["quick".to_string()];
+        t.estimate = Some("P3D".to_string()); // beyond the last band
+        assert_eq!(suggest(&config, &history, &t).challenge, 8);
+
+        config.tags.insert("epic".to_string(), 9);
+        t.tags.push("epic".to_string());
+        assert_eq!(suggest(&config, &history, &t).challenge, 10);
+    }
+
+    #[test]
+    fn test_history_prefers_similar_tasks() {
+        let config = ChallengeConfig::default();
+        let mut history = CompletionHistory::new();
+        for (description, challenge) in [
+            ("Refactor login module", 8),
+            ("Refactor search module", 7),
+            ("Refactor billing module", 8),
+            ("Update docs", 2),
+        ] {
+            history.record(record(description, "app", challenge));
+        }
+
+        let similar = suggest(&config, &history, &task("Refactor the payment module", Some("app")));
+        assert_eq!(similar.challenge, 8);
+        assert_eq!(similar.base_reason, "average of 3 similar quests in app");
+
+        // Nothing similar: the whole project
+        let other = suggest(&config, &history, &task("Plan sprint", Some("app")));
+        assert_eq!(other.base, 6.25);
+        assert_eq!(other.challenge, 6);
+
+        // Not enough history in the project
+        assert_eq!(suggest(&config, &history, &task("Fix bike", Some("home"))).base_reason, "default");
+    }
+}
@@ -0,0 +1,38 @@
+use anyhow::{Context, Result};
+use std::process::Command;
+
+use super::parser::TaskData;
+
+/// Tasks matching a filter, as reported by `task export` (hooks off, so
+/// exporting doesn't trigger TaskQuest itself)
+pub fn export(filter: &[&str]) -> Result<Vec<TaskData>> {
+    let output = Command::new("task")
+        .args(["rc.hooks=off", "rc.verbose=nothing", "rc.json.array=on"])
+        .args(filter)
+        .arg("export")
+        .output()
+        .context("Failed to run 'task export'. Is Taskwarrior installed?")?;
+
+    if !output.status.success() {
+        anyhow::bail!("'task export' failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    parse(&String::from_utf8_lossy(&output.stdout))
+}
+
+pub fn parse(text: &str) -> Result<Vec<TaskData>> {
+    serde_json::from_str(text).context("Failed to parse Taskwarrior export")
+}
+
+/// The task with this ID or UUID
+pub fn find(id: &str) -> Result<TaskData> {
+    let is_id = id.chars().all(|c| c.is_ascii_digit());
+    let is_uuid = id.len() >= 8 && id.chars().all(|c| c.is_ascii_hexdigit() || c == '-');
+    if !is_id && !is_uuid {
+        anyhow::bail!("Invalid task: {}. Use a task ID or UUID", id);
+    }
+
+    export(&[id])?
+        .into_iter()
+        .next()
+        .with_context(|| format!("No task {}", id))
+}
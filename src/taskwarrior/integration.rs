@@ -1,94 +1,156 @@
 use anyhow::Result;
-use chrono::Utc;
-use super::parser::TaskData;
+use chrono::{DateTime, Utc};
 use crate::character::Character;
-use crate::progression::{XPCalculator, GoldCalculator, LootSystem, LootDrop};
-use crate::storage::{get_data_dir, safe_write, load_with_backup};
-use crate::achievements::AchievementTracker;
+use crate::events::Events;
+use crate::game::{Completion, Profile};
+use crate::history::habits;
+use crate::display::Formatter;
+use crate::progression::{LootDrop, RewardBreakdown};
+use crate::storage::backups::Backups;
+use super::parser::TaskData;
+use super::reconcile;
 
 pub struct TaskwarriorIntegration;
 
 impl TaskwarriorIntegration {
-    /// Process a task completion
-    pub fn process_completion(task: &TaskData) -> Result<()> {
+    /// Process a task completion, returning what it earned
+    pub fn process_completion(task: &TaskData) -> Result<Option<Completion>> {
         if !task.is_completed() {
-            return Ok(());
+            return Ok(None);
         }
 
-        let data_dir = get_data_dir()?;
-        let character_path = data_dir.join("character.json");
-        let achievements_path = data_dir.join("achievements.json");
+        let mut profile = Profile::open_active()?;
 
-        // Load character
-        let mut character: Character = load_with_backup(&character_path)?;
+        // Periodic snapshot of the state before this quest is applied
+        if let Err(e) = Backups::auto_snapshot(profile.data_dir(), &profile.config.backup) {
+            eprintln!("Warning: Failed to create snapshot: {}", e);
+        }
 
-        // Load achievement tracker
-        let mut tracker = AchievementTracker::load(&achievements_path)
-            .unwrap_or_else(|_| AchievementTracker::new());
+        let completion = profile.complete_task(task);
+        profile.save()?;
 
-        // Calculate rewards
-        let challenge = task.get_challenge();
-        let urgency = task.get_urgency();
+        Events::emit(&profile.config.events, &profile.character.name, &completion.events());
 
-        // Determine timing
-        let completion_time = task.get_completion_date().unwrap_or_else(Utc::now);
-        let timing = XPCalculator::determine_timing(task.get_due_date(), completion_time);
+        // Print reward notification
+        let loot_info = match completion.record.loot {
+            Some(LootDrop::Reward { tier, ref name }) => Some((tier, name.clone())),
+            _ => None,
+        };
+        Self::print_rewards(
+            completion.record.xp,
+            completion.record.gold,
+            completion.record.bonus_gold,
+            &profile.character,
+            loot_info.as_ref(),
+            completion.record.breakdown.as_ref().filter(|_| Self::verbose()),
+        );
 
-        // Calculate XP and gold
-        let xp = XPCalculator::calculate(challenge, urgency, timing);
-        let base_gold = GoldCalculator::calculate(challenge);
+        if let Some(minutes) = completion.record.focus_minutes {
+            println!("🎧 +{} XP focus bonus ({} min tracked)", completion.record.focus_xp, minutes);
+        }
+        if let Some(multiplier) = completion.record.trivial_multiplier {
+            println!("🩶 Trivial at level {}: challenge {} pays {:.0}% rewards and loot chance",
+                completion.level_before, completion.record.xp_challenge.unwrap_or(completion.record.challenge), multiplier * 100.0);
+        }
+        if let Some(multiplier) = completion.record.instant_multiplier {
+            println!("⚡ Completed right after it was added: {:.0}% rewards and loot chance", multiplier * 100.0);
+        }
+        let day = completion.record.completed_at().unwrap_or_else(Utc::now).date_naive();
+        if let Some(habit) = habits::standing_on(&profile.config.habits, &profile.rest_days(), &profile.history, task, day) {
+            println!("🔁 Habit streak {} · strength {:.0}% (+{} XP, +{} Gold habit bonus)",
+                habit.streak, habit.strength * 100.0, completion.record.habit_xp, completion.record.habit_gold);
+        }
+        if let (Some(capped), Some(minutes)) = (completion.record.xp_challenge, completion.record.duration_minutes()) {
+            println!("⚖️  Rewarded as challenge {} (rated {}, done in {:.0} min)",
+                capped, completion.record.challenge, minutes);
+        }
 
-        // Roll for loot
-        let loot_drop = LootSystem::roll_for_loot(challenge);
-        let mut bonus_gold = 0;
-        let mut loot_info = None;
+        // Print achievement notifications
+        if !completion.achievements.is_empty() {
+            Self::print_achievements(&completion.achievements);
+        }
 
-        if let Some(ref loot) = loot_drop {
-            match loot {
-                LootDrop::Gold(amount) => {
-                    bonus_gold = *amount;
-                }
-                LootDrop::Reward { tier, name } => {
-                    loot_info = Some((*tier, name.clone()));
-                }
-            }
+        // Print newly earned titles
+        for title in &completion.new_titles {
+            Self::print_title_unlocked(title);
+        }
 
-            // Track loot drop in achievements
-            let new_achievements = tracker.record_loot_drop(loot, &character);
-            if !new_achievements.is_empty() {
-                Self::print_achievements(&new_achievements);
-            }
+        Ok(Some(completion))
+    }
+
+    /// Itemise rewards in the hook's output (`TASKQUEST_VERBOSE=1`)
+    fn verbose() -> bool {
+        std::env::var("TASKQUEST_VERBOSE").is_ok_and(|v| !v.is_empty() && v != "0")
+    }
+
+    /// Credit completed tasks the hook never saw. With `dry_run`, only show
+    /// what they would earn; with `verbose`, itemise each quest's rewards.
+    pub fn reconcile(tasks: Vec<TaskData>, since: Option<DateTime<Utc>>, dry_run: bool, verbose: bool) -> Result<()> {
+        use colored::Colorize;
+
+        let profile = Profile::open_active()?;
+        let since = since.or_else(|| reconcile::default_since(&profile));
+        let missed = reconcile::find_missed(&profile, tasks, since);
+
+        if let Some(since) = since {
+            println!("Looking at tasks completed since {}", since.format("%Y-%m-%d %H:%M"));
+        }
+        if missed.is_empty() {
+            println!("✅ Every completed task has been credited");
+            return Ok(());
         }
 
-        // Update character
-        let total_gold = base_gold + bonus_gold;
-        character.add_xp(xp);
-        character.add_gold(total_gold);
+        if !dry_run {
+            Backups::create(profile.data_dir(), "before reconcile", &profile.config.backup)?;
+        }
 
-        // Get stats from task (if specified)
-        let stat1 = task.get_stat1();
-        let stat2 = task.get_stat2();
-        character.complete_task(challenge, stat1, stat2);
+        // A dry run credits a throwaway copy, so the numbers are the real ones
+        let mut target = if dry_run { profile.clone() } else { profile };
+        let level_before = target.character.level;
+        let (mut xp, mut gold) = (0, 0);
+        let mut completions = Vec::new();
 
-        // Track quest completion in achievements
-        let project = task.get_project();
-        let new_achievements = tracker.record_quest_completion(
-            &character,
-            challenge,
-            timing,
-            project.as_deref(),
-        );
+        println!();
+        for task in &missed {
+            let completion = target.complete_logged_task(task);
+            let record = &completion.record;
+            println!("  {}  {}  {} XP  {} Gold",
+                record.completed_at().map(|t| t.format("%Y-%m-%d").to_string()).unwrap_or_default(),
+                record.description,
+                format!("+{}", record.xp).green(),
+                format!("+{}", record.total_gold()).yellow()
+            );
+            if let Some(breakdown) = record.breakdown.as_ref().filter(|_| verbose) {
+                for line in Formatter::reward_breakdown_lines(breakdown) {
+                    println!("      {}", line);
+                }
+            }
+            xp += record.xp;
+            gold += record.total_gold();
+            completions.push(completion);
+        }
+        println!();
 
-        // Save everything
-        safe_write(&character_path, &character)?;
-        safe_write(&achievements_path, &tracker)?;
+        if dry_run {
+            println!("Would credit {} quest(s): +{} XP, +{} Gold", missed.len(), xp, gold);
+            println!("Loot is rolled when the quests are credited, so bonus gold may differ.");
+            println!("Run without --dry-run to apply.");
+            return Ok(());
+        }
 
-        // Print reward notification
-        Self::print_rewards(xp, base_gold, bonus_gold, &character, loot_info.as_ref());
+        target.save()?;
+        let events: Vec<_> = completions.iter().flat_map(|c| c.events()).collect();
+        Events::emit(&target.config.events, &target.character.name, &events);
 
-        // Print achievement notifications
-        if !new_achievements.is_empty() {
-            Self::print_achievements(&new_achievements);
+        println!("✅ Credited {} quest(s): +{} XP, +{} Gold", missed.len(), xp, gold);
+        if target.character.level > level_before {
+            println!("⬆️  Level {} → {}", level_before, target.character.level);
+        }
+        for completion in &completions {
+            Self::print_achievements(&completion.achievements);
+            for title in &completion.new_titles {
+                Self::print_title_unlocked(title);
+            }
         }
 
         Ok(())
@@ -100,6 +162,7 @@ impl TaskwarriorIntegration {
         bonus_gold: u32,
         character: &Character,
         loot: Option<&(crate::progression::RewardTier, String)>,
+        breakdown: Option<&RewardBreakdown>,
     ) {
         use colored::Colorize;
 
@@ -107,6 +170,8 @@ impl TaskwarriorIntegration {
         println!("{}", "╔════════════════════════════════════════╗".cyan());
         println!("{}", "║       ⚔️  QUEST COMPLETE! ⚔️            ║".cyan().bold());
         println!("{}", "╠════════════════════════════════════════╣".cyan());
+        println!("║ {}                                ║", character.display_name().bold());
+        println!("{}", "╠════════════════════════════════════════╣".cyan());
 
         if bonus_gold > 0 {
             println!("║ {} XP  │  {} Gold (+ {} bonus!)  ║",
@@ -121,6 +186,13 @@ impl TaskwarriorIntegration {
             );
         }
 
+        if let Some(breakdown) = breakdown {
+            println!("{}", "╠════════════════════════════════════════╣".cyan());
+            for line in Formatter::reward_breakdown_lines(breakdown) {
+                println!("║ {}", line);
+            }
+        }
+
         // Display loot drop if present
         if let Some((tier, name)) = loot {
             println!("{}", "╠════════════════════════════════════════╣".cyan());
@@ -143,10 +215,28 @@ impl TaskwarriorIntegration {
             character.total_xp + character.xp_to_next_level()
         );
         println!("║ Gold: {}                               ║", character.gold);
+
+        for goal in &character.savings_goals {
+            println!("║ 🎯 {} {} {}/{}",
+                goal.reward_name,
+                goal.bar(10).yellow(),
+                goal.reserved,
+                goal.target
+            );
+        }
         println!("{}", "╚════════════════════════════════════════╝".cyan());
         println!();
     }
 
+    fn print_title_unlocked(title: &crate::achievements::Title) {
+        use colored::Colorize;
+
+        println!("🎖️  New title unlocked: {} (use 'taskquest title set {}')",
+            title.name.yellow().bold(),
+            title.id
+        );
+    }
+
     fn print_achievements(achievements: &[&crate::achievements::Achievement]) {
         use colored::Colorize;
 
@@ -1,7 +1,11 @@
 pub mod parser;
 pub mod integration;
 pub mod uda;
+pub mod reconcile;
+pub mod challenge;
+pub mod export;
 
 pub use parser::TaskData;
 pub use integration::TaskwarriorIntegration;
 pub use uda::UDAManager;
+pub use challenge::{ChallengeConfig, Suggestion};
@@ -6,7 +6,6 @@ fn deserialize_challenge<'de, D>(deserializer: D) -> Result<Option<u8>, D::Error
 where
     D: Deserializer<'de>,
 {
-    use serde::de::Error;
     let value: Option<serde_json::Value> = Option::deserialize(deserializer)?;
 
     match value {
@@ -24,20 +23,42 @@ where
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Parse a Taskwarrior date: `task export` and hooks use the compact ISO form
+/// (`20240315T093000Z`); RFC 3339 is accepted too
+pub fn parse_task_date(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    chrono::NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ")
+        .ok()
+        .map(|dt| dt.and_utc())
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TaskData {
     pub id: Option<u32>,
     pub uuid: String,
     pub status: String,
     pub description: String,
     pub urgency: Option<f64>,
+    pub entry: Option<String>,
     pub due: Option<String>,
+    pub start: Option<String>,
     pub end: Option<String>,
-    #[serde(deserialize_with = "deserialize_challenge")]
+    #[serde(default, deserialize_with = "deserialize_challenge")]
     pub challenge: Option<u8>,
     pub project: Option<String>,
     pub stat1: Option<String>,
     pub stat2: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub priority: Option<String>,
+    /// Optional duration UDA, used to suggest a challenge
+    pub estimate: Option<String>,
+    /// Recurrence period, set on recurring templates and their instances
+    pub recur: Option<String>,
+    /// UUID of the recurring template an instance was created from
+    pub parent: Option<String>,
 }
 
 impl TaskData {
@@ -58,16 +79,22 @@ impl TaskData {
 
     /// Parse due date
     pub fn get_due_date(&self) -> Option<DateTime<Utc>> {
-        self.due.as_ref()
-            .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
-            .map(|dt| dt.with_timezone(&Utc))
+        self.due.as_deref().and_then(parse_task_date)
+    }
+
+    /// Parse the date the task was added
+    pub fn get_entry_date(&self) -> Option<DateTime<Utc>> {
+        self.entry.as_deref().and_then(parse_task_date)
+    }
+
+    /// Parse start date (set while the task is active)
+    pub fn get_start_date(&self) -> Option<DateTime<Utc>> {
+        self.start.as_deref().and_then(parse_task_date)
     }
 
     /// Parse completion date
     pub fn get_completion_date(&self) -> Option<DateTime<Utc>> {
-        self.end.as_ref()
-            .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
-            .map(|dt| dt.with_timezone(&Utc))
+        self.end.as_deref().and_then(parse_task_date)
     }
 
     /// Check if task was completed
@@ -80,6 +107,15 @@ impl TaskData {
         self.project.clone()
     }
 
+    /// The habit a recurring task belongs to: its template's UUID
+    pub fn habit_id(&self) -> Option<String> {
+        match (&self.parent, &self.recur) {
+            (Some(parent), _) => Some(parent.to_lowercase()),
+            (None, Some(_)) => Some(self.uuid.to_lowercase()),
+            (None, None) => None,
+        }
+    }
+
     /// Get stat1 as StatType
     pub fn get_stat1(&self) -> Option<crate::character::StatType> {
         self.stat1.as_ref()
@@ -125,4 +161,36 @@ mod tests {
         let task = TaskData::from_json(json).unwrap();
         assert_eq!(task.get_challenge(), 5);
     }
+
+    #[test]
+    fn test_habit_id() {
+        let instance = TaskData::from_json(r#"{
+            "uuid": "2", "status": "pending", "description": "Stretch",
+            "recur": "daily", "parent": "ABC-1"
+        }"#).unwrap();
+        assert_eq!(instance.habit_id().as_deref(), Some("abc-1"));
+
+        let template = TaskData::from_json(r#"{
+            "uuid": "ABC-1", "status": "recurring", "description": "Stretch", "recur": "daily"
+        }"#).unwrap();
+        assert_eq!(template.habit_id(), instance.habit_id());
+
+        let one_off = TaskData::from_json(r#"{"uuid": "3", "status": "pending", "description": "Once"}"#).unwrap();
+        assert_eq!(one_off.habit_id(), None);
+    }
+
+    #[test]
+    fn test_taskwarrior_dates() {
+        let json = r#"{
+            "uuid": "12345678-1234-1234-1234-123456789012",
+            "status": "completed",
+            "description": "Test task",
+            "due": "2024-03-16T00:00:00Z",
+            "end": "20240315T093000Z"
+        }"#;
+
+        let task = TaskData::from_json(json).unwrap();
+        assert_eq!(task.get_completion_date().unwrap().to_rfc3339(), "2024-03-15T09:30:00+00:00");
+        assert_eq!(task.get_due_date().unwrap().to_rfc3339(), "2024-03-16T00:00:00+00:00");
+    }
 }
@@ -0,0 +1,115 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use std::path::Path;
+
+use super::export;
+use super::parser::TaskData;
+use crate::game::Profile;
+
+/// Completed tasks from Taskwarrior
+pub fn export_completed() -> Result<Vec<TaskData>> {
+    Ok(export::export(&["status:completed"])?
+        .into_iter()
+        .filter(|t| t.is_completed())
+        .collect())
+}
+
+/// Completed tasks from a saved `task export` file
+pub fn read_export(path: &Path) -> Result<Vec<TaskData>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    parse_export(&text)
+}
+
+fn parse_export(text: &str) -> Result<Vec<TaskData>> {
+    Ok(export::parse(text)?.into_iter().filter(|t| t.is_completed()).collect())
+}
+
+/// How far back reconcile looks when no `--since` is given.
+///
+/// History only covers completions since it was introduced. If the character
+/// has credits from before that, tasks older than the first history entry may
+/// already have been rewarded, so they are left alone.
+pub fn default_since(profile: &Profile) -> Option<DateTime<Utc>> {
+    let history = &profile.history.completions;
+    if (profile.character.tasks_completed as usize) <= history.len() {
+        return None;
+    }
+    Some(history.iter()
+        .filter_map(|r| r.completed_at())
+        .min()
+        .unwrap_or_else(Utc::now))
+}
+
+/// Completed tasks that were never credited, oldest first.
+/// Tasks without an end date can't be placed in time and are skipped.
+pub fn find_missed(profile: &Profile, tasks: Vec<TaskData>, since: Option<DateTime<Utc>>) -> Vec<TaskData> {
+    let credited: HashSet<String> = profile.history.completions.iter()
+        .map(|r| r.uuid.to_lowercase())
+        .collect();
+
+    let mut missed: Vec<TaskData> = tasks.into_iter()
+        .filter(|t| !credited.contains(&t.uuid.to_lowercase()))
+        .filter(|t| match (t.get_completion_date(), since) {
+            (None, _) => false,
+            (Some(end), Some(since)) => end >= since,
+            (Some(_), None) => true,
+        })
+        .collect();
+
+    missed.sort_by_key(|t| t.get_completion_date());
+    missed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character::{Character, Class};
+    use crate::storage::safe_write;
+
+    fn task(uuid: &str, end: Option<&str>) -> TaskData {
+        TaskData {
+            uuid: uuid.to_string(),
+            status: "completed".to_string(),
+            description: format!("Task {}", uuid),
+            end: end.map(|e| e.to_string()),
+            challenge: Some(5),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_find_missed() {
+        let dir = tempfile::tempdir().unwrap();
+        safe_write(&dir.path().join("character.json"), &Character::new("Test".to_string(), Class::Warrior)).unwrap();
+        let mut profile = Profile::open(dir.path()).unwrap();
+        profile.complete_task(&task("A", Some("20240301T120000Z")));
+
+        let tasks = vec![
+            task("c", Some("20240305T120000Z")),
+            task("a", Some("20240301T120000Z")), // credited (uuids ignore case)
+            task("b", Some("20240302T120000Z")),
+            task("d", None),
+        ];
+        let missed = find_missed(&profile, tasks.clone(), None);
+        assert_eq!(missed.iter().map(|t| t.uuid.as_str()).collect::<Vec<_>>(), ["b", "c"]);
+
+        let since = "2024-03-04T00:00:00Z".parse().unwrap();
+        assert_eq!(find_missed(&profile, tasks, Some(since)).len(), 1);
+
+        // Credits from before history existed push the default window forward
+        assert_eq!(default_since(&profile), None);
+        profile.character.tasks_completed += 5;
+        assert_eq!(default_since(&profile).unwrap().to_rfc3339(), "2024-03-01T12:00:00+00:00");
+    }
+
+    #[test]
+    fn test_parse_export_keeps_completed() {
+        let tasks = parse_export(r#"[
+            {"uuid": "1", "status": "completed", "description": "done", "end": "20240301T120000Z"},
+            {"uuid": "2", "status": "pending", "description": "todo"}
+        ]"#).unwrap();
+        assert_eq!(tasks.len(), 1);
+    }
+}
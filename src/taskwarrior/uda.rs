@@ -1,8 +1,62 @@
 use anyhow::{Context, Result};
 use std::fs::OpenOptions;
-use std::io::{BufRead, BufReader, Write};
+use std::io::Write;
 use std::path::PathBuf;
 
+/// UDA holding the XP a quest earned (projected XP while it is pending)
+pub const XP: &str = "tq_xp";
+/// UDA holding the gold a quest earned, loot gold included
+pub const GOLD: &str = "tq_gold";
+/// UDA naming the loot a quest dropped
+pub const LOOT: &str = "tq_loot";
+
+/// UDA definitions for .taskrc, keyed by UDA name. Each block is added on its
+/// own so existing setups pick up UDAs introduced later.
+const UDA_BLOCKS: &[(&str, &str)] = &[
+    ("challenge", "\
+# TaskQuest UDAs
+uda.challenge.type=numeric
+uda.challenge.label=Challenge
+uda.challenge.values=1,2,3,4,5,6,7,8,9,10
+
+# Optional: Color coding for challenge levels
+color.uda.challenge.1=color246  # Trivial - gray
+color.uda.challenge.2=color246
+color.uda.challenge.3=color250  # Easy - lighter gray
+color.uda.challenge.4=color250
+color.uda.challenge.5=color255  # Medium - white
+color.uda.challenge.6=color255
+color.uda.challenge.7=color226  # Hard - yellow
+color.uda.challenge.8=color208  # Deadly - orange
+color.uda.challenge.9=color196  # Legendary - red
+color.uda.challenge.10=color201 # Epic - magenta
+"),
+    ("stat1", "\
+# Stats trained by this task (66% to stat1, 33% to stat2)
+uda.stat1.type=string
+uda.stat1.label=Primary Stat
+uda.stat1.values=STR,DEX,CON,INT,WIS,CHA
+"),
+    ("stat2", "\
+uda.stat2.type=string
+uda.stat2.label=Secondary Stat
+uda.stat2.values=STR,DEX,CON,INT,WIS,CHA
+"),
+    ("tq_xp", "\
+# What each quest earned, written by TaskQuest (tq_xp is projected until done)
+uda.tq_xp.type=numeric
+uda.tq_xp.label=XP
+"),
+    ("tq_gold", "\
+uda.tq_gold.type=numeric
+uda.tq_gold.label=Gold
+"),
+    ("tq_loot", "\
+uda.tq_loot.type=string
+uda.tq_loot.label=Loot
+"),
+];
+
 pub struct UDAManager;
 
 impl UDAManager {
@@ -12,29 +66,42 @@ impl UDAManager {
         Ok(PathBuf::from(home).join(".taskrc"))
     }
 
-    /// Check if UDAs are already configured
-    pub fn is_configured() -> Result<bool> {
+    /// UDA definitions not yet in .taskrc
+    fn missing_blocks() -> Result<Vec<(&'static str, &'static str)>> {
         let taskrc = Self::get_taskrc_path()?;
-        if !taskrc.exists() {
-            return Ok(false);
-        }
+        let contents = if taskrc.exists() {
+            std::fs::read_to_string(&taskrc)?
+        } else {
+            String::new()
+        };
 
-        let file = std::fs::File::open(&taskrc)?;
-        let reader = BufReader::new(file);
+        Ok(Self::missing_in(&contents))
+    }
 
-        for line in reader.lines() {
-            let line = line?;
-            if line.contains("uda.challenge.type") {
-                return Ok(true);
-            }
-        }
+    fn missing_in(taskrc: &str) -> Vec<(&'static str, &'static str)> {
+        UDA_BLOCKS.iter()
+            .filter(|(name, _)| {
+                let key = format!("uda.{}.type", name);
+                !taskrc.lines().any(|l| l.trim_start().starts_with(&key))
+            })
+            .copied()
+            .collect()
+    }
 
-        Ok(false)
+    /// Names of TaskQuest UDAs missing from .taskrc
+    pub fn missing() -> Result<Vec<&'static str>> {
+        Ok(Self::missing_blocks()?.into_iter().map(|(name, _)| name).collect())
     }
 
-    /// Add TaskQuest UDAs to .taskrc
+    /// Check if UDAs are already configured
+    pub fn is_configured() -> Result<bool> {
+        Ok(Self::missing_blocks()?.is_empty())
+    }
+
+    /// Add any missing TaskQuest UDAs to .taskrc
     pub fn configure() -> Result<()> {
-        if Self::is_configured()? {
+        let missing = Self::missing_blocks()?;
+        if missing.is_empty() {
             return Ok(());
         }
 
@@ -44,34 +111,33 @@ impl UDAManager {
             .append(true)
             .open(&taskrc)?;
 
-        writeln!(file, "\n# TaskQuest UDAs")?;
-        writeln!(file, "uda.challenge.type=numeric")?;
-        writeln!(file, "uda.challenge.label=Challenge")?;
-        writeln!(file, "uda.challenge.values=1,2,3,4,5,6,7,8,9,10")?;
-        writeln!(file)?;
-        writeln!(file, "# Stats trained by this task (66% to stat1, 33% to stat2)")?;
-        writeln!(file, "uda.stat1.type=string")?;
-        writeln!(file, "uda.stat1.label=Primary Stat")?;
-        writeln!(file, "uda.stat1.values=STR,DEX,CON,INT,WIS,CHA")?;
-        writeln!(file)?;
-        writeln!(file, "uda.stat2.type=string")?;
-        writeln!(file, "uda.stat2.label=Secondary Stat")?;
-        writeln!(file, "uda.stat2.values=STR,DEX,CON,INT,WIS,CHA")?;
-        writeln!(file)?;
-        writeln!(file, "# Optional: Color coding for challenge levels")?;
-        writeln!(file, "color.uda.challenge.1=color246  # Trivial - gray")?;
-        writeln!(file, "color.uda.challenge.2=color246")?;
-        writeln!(file, "color.uda.challenge.3=color250  # Easy - lighter gray")?;
-        writeln!(file, "color.uda.challenge.4=color250")?;
-        writeln!(file, "color.uda.challenge.5=color255  # Medium - white")?;
-        writeln!(file, "color.uda.challenge.6=color255")?;
-        writeln!(file, "color.uda.challenge.7=color226  # Hard - yellow")?;
-        writeln!(file, "color.uda.challenge.8=color208  # Deadly - orange")?;
-        writeln!(file, "color.uda.challenge.9=color196  # Legendary - red")?;
-        writeln!(file, "color.uda.challenge.10=color201 # Epic - magenta")?;
-
-        println!("TaskQuest UDAs added to ~/.taskrc");
+        for (_, block) in &missing {
+            writeln!(file)?;
+            write!(file, "{}", block)?;
+        }
+
+        println!("TaskQuest UDAs added to ~/.taskrc: {}",
+            missing.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(", "));
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_missing_udas_are_added() {
+        let names = |taskrc: &str| UDAManager::missing_in(taskrc).into_iter().map(|(n, _)| n).collect::<Vec<_>>();
+
+        assert_eq!(names("").len(), UDA_BLOCKS.len());
+        assert_eq!(
+            names("uda.challenge.type=numeric\nuda.stat1.type=string\nuda.stat2.type=string\n"),
+            [XP, GOLD, LOOT]
+        );
+
+        let all: String = UDA_BLOCKS.iter().map(|(_, block)| *block).collect();
+        assert!(names(&all).is_empty());
+    }
+}
@@ -0,0 +1,368 @@
+//! Timewarrior integration: focus time tracked on a task earns bonus XP, and
+//! the day's total tracked time counts toward the deep work achievements.
+//!
+//! Intervals are read on demand from `timew export` or straight from
+//! Timewarrior's data files; nothing runs in the background.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::taskwarrior::parser::parse_task_date;
+use crate::taskwarrior::TaskData;
+
+/// Tracked minutes that make a day a deep work day
+pub const DEEP_WORK_MINUTES: u32 = 240;
+
+/// Where intervals are read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimewSource {
+    /// Run `timew export`
+    Export,
+    /// Read `data/*.data` in the Timewarrior database
+    Files,
+}
+
+/// `timewarrior` section of config.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimewConfig {
+    pub enabled: bool,
+    pub source: TimewSource,
+    /// Timewarrior database for `files` (default: $TIMEWARRIORDB,
+    /// ~/.timewarrior or ~/.local/share/timewarrior)
+    pub db: Option<PathBuf>,
+    /// Also count intervals that share a tag with the task. Intervals tagged
+    /// with the task's UUID or description always count.
+    pub match_tags: bool,
+    /// Bonus XP per hour of focus time tracked on a quest
+    pub xp_per_hour: u32,
+    /// Focus hours a single quest can earn a bonus for
+    pub max_bonus_hours: f64,
+}
+
+impl Default for TimewConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            source: TimewSource::Export,
+            db: None,
+            match_tags: false,
+            xp_per_hour: 10,
+            max_bonus_hours: 4.0,
+        }
+    }
+}
+
+/// A tracked interval. Open intervals (still tracking) have no end.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interval {
+    pub start: DateTime<Utc>,
+    pub end: Option<DateTime<Utc>>,
+    pub tags: Vec<String>,
+}
+
+/// An interval as `timew export` writes it
+#[derive(Deserialize)]
+struct ExportedInterval {
+    start: String,
+    end: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+impl Interval {
+    fn end_or(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        self.end.unwrap_or(now)
+    }
+
+    /// Tracked for this task: tagged with its UUID or description (as the
+    /// Taskwarrior hook shipped with Timewarrior does), or sharing a tag
+    fn is_for(&self, task: &TaskData, match_tags: bool) -> bool {
+        self.tags.iter().any(|tag| {
+            tag.eq_ignore_ascii_case(&task.uuid)
+                || *tag == task.description
+                || (match_tags && task.tags.contains(tag))
+        })
+    }
+}
+
+/// Time tracked on one day
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DailyFocus {
+    pub minutes: u32,
+    /// Longest stretch without a gap, in minutes
+    pub longest_block: u32,
+}
+
+/// Merge overlapping spans and return them in order
+fn merge(mut spans: Vec<(DateTime<Utc>, DateTime<Utc>)>) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    spans.retain(|(start, end)| end > start);
+    spans.sort();
+
+    let mut merged: Vec<(DateTime<Utc>, DateTime<Utc>)> = Vec::new();
+    for (start, end) in spans {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+fn minutes(spans: &[(DateTime<Utc>, DateTime<Utc>)]) -> f64 {
+    spans.iter().map(|(start, end)| (*end - *start).num_seconds() as f64 / 60.0).sum()
+}
+
+/// Minutes tracked on a task between `from` and `to`; overlapping intervals
+/// count once
+pub fn focus_minutes(
+    intervals: &[Interval],
+    task: &TaskData,
+    match_tags: bool,
+    from: Option<DateTime<Utc>>,
+    to: DateTime<Utc>,
+) -> f64 {
+    let spans = intervals.iter()
+        .filter(|i| i.is_for(task, match_tags))
+        .map(|i| (from.map_or(i.start, |from| i.start.max(from)), i.end_or(to).min(to)))
+        .collect();
+    minutes(&merge(spans))
+}
+
+/// Bonus XP for focus time on a quest
+pub fn focus_xp(config: &TimewConfig, minutes: f64) -> u32 {
+    let hours = (minutes / 60.0).min(config.max_bonus_hours.max(0.0));
+    (hours * config.xp_per_hour as f64).round() as u32
+}
+
+/// All time tracked on a day (UTC), whatever it was tracked on
+pub fn daily_focus(intervals: &[Interval], day: NaiveDate, now: DateTime<Utc>) -> DailyFocus {
+    let day_start = day.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+    let day_end = day_start + Duration::days(1);
+
+    let spans = merge(intervals.iter()
+        .map(|i| (i.start.max(day_start), i.end_or(now).min(day_end)))
+        .collect());
+
+    DailyFocus {
+        minutes: minutes(&spans).round() as u32,
+        longest_block: spans.iter()
+            .map(|(start, end)| (*end - *start).num_minutes() as u32)
+            .max()
+            .unwrap_or(0),
+    }
+}
+
+/// Read intervals from an exported JSON file (`timew export > file.json`)
+pub fn read_export(path: &Path) -> Result<Vec<Interval>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    parse_export(&text)
+}
+
+fn parse_export(text: &str) -> Result<Vec<Interval>> {
+    let exported: Vec<ExportedInterval> = serde_json::from_str(text)
+        .context("Failed to parse Timewarrior export")?;
+
+    Ok(exported.into_iter()
+        .filter_map(|i| Some(Interval {
+            start: parse_task_date(&i.start)?,
+            end: i.end.as_deref().and_then(parse_task_date),
+            tags: i.tags,
+        }))
+        .collect())
+}
+
+/// Split the tag part of a data file line, honouring double quotes
+fn split_tags(text: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if quoted => current.extend(chars.next()),
+            '"' => quoted = !quoted,
+            ' ' if !quoted => {
+                if !current.is_empty() {
+                    tags.push(std::mem::take(&mut current));
+                }
+            }
+            _ => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tags.push(current);
+    }
+    tags
+}
+
+/// Parse one `inc <start> [- <end>] [# tags [# annotation]]` line
+fn parse_data_line(line: &str) -> Option<Interval> {
+    let rest = line.trim().strip_prefix("inc ")?;
+    let (range, tags) = match rest.split_once(" # ") {
+        Some((range, tags)) => (range, tags.split(" # ").next().unwrap_or("")),
+        None => (rest.trim_end_matches(" #"), ""),
+    };
+
+    let mut parts = range.split_whitespace();
+    let start = parse_task_date(parts.next()?)?;
+    let end = match (parts.next(), parts.next()) {
+        (Some("-"), Some(end)) => Some(parse_task_date(end)?),
+        _ => None,
+    };
+
+    Some(Interval { start, end, tags: split_tags(tags) })
+}
+
+/// Default location of the Timewarrior database
+fn default_db() -> Option<PathBuf> {
+    if let Ok(db) = std::env::var("TIMEWARRIORDB") {
+        return Some(PathBuf::from(db));
+    }
+    let home = PathBuf::from(std::env::var("HOME").ok()?);
+    let legacy = home.join(".timewarrior");
+    if legacy.exists() {
+        return Some(legacy);
+    }
+    let data_home = std::env::var("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| home.join(".local/share"));
+    Some(data_home.join("timewarrior"))
+}
+
+/// Read intervals from the monthly data files, skipping months before `since`
+pub fn read_data_files(db: &Path, since: Option<NaiveDate>) -> Result<Vec<Interval>> {
+    let data_dir = db.join("data");
+    let entries = std::fs::read_dir(&data_dir)
+        .with_context(|| format!("Failed to read {}", data_dir.display()))?;
+    let first_month = since.map(|d| format!("{:04}-{:02}", d.year(), d.month()));
+
+    let mut intervals = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let Some(month) = path.file_name().and_then(|n| n.to_str()).and_then(|n| n.strip_suffix(".data")) else {
+            continue;
+        };
+        if first_month.as_deref().is_some_and(|first| month < first) {
+            continue;
+        }
+
+        let text = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        intervals.extend(text.lines().filter_map(parse_data_line));
+    }
+
+    intervals.sort_by_key(|i| i.start);
+    Ok(intervals)
+}
+
+/// Intervals from the configured source, from `since` on. Data files are
+/// read by whole months, so earlier days of the first month may be included.
+pub fn load(config: &TimewConfig, since: Option<NaiveDate>) -> Result<Vec<Interval>> {
+    match config.source {
+        TimewSource::Export => {
+            let mut command = Command::new("timew");
+            command.arg("export");
+            if let Some(since) = since {
+                command.args(["from", &since.format("%Y-%m-%d").to_string()]);
+            }
+            let output = command.output()
+                .context("Failed to run 'timew export'. Is Timewarrior installed?")?;
+            if !output.status.success() {
+                anyhow::bail!("'timew export' failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+            }
+            parse_export(&String::from_utf8_lossy(&output.stdout))
+        }
+        TimewSource::Files => {
+            let db = config.db.clone().or_else(default_db)
+                .context("Timewarrior database not found; set timewarrior.db in config.json")?;
+            read_data_files(&db, since)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(time: &str) -> DateTime<Utc> {
+        parse_task_date(time).unwrap()
+    }
+
+    fn interval(start: &str, end: Option<&str>, tags: &[&str]) -> Interval {
+        Interval {
+            start: at(start),
+            end: end.map(at),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_data_line() {
+        let line = r#"inc 20260105T090000Z - 20260105T103000Z # "Write report" work 5f2a3c1e # "annotated""#;
+        let parsed = parse_data_line(line).unwrap();
+        assert_eq!(parsed, interval("20260105T090000Z", Some("20260105T103000Z"), &["Write report", "work", "5f2a3c1e"]));
+
+        let open = parse_data_line("inc 20260105T110000Z # work").unwrap();
+        assert_eq!(open.end, None);
+        assert!(parse_data_line("inc 20260105T110000Z").unwrap().tags.is_empty());
+        assert!(parse_data_line("# comment").is_none());
+    }
+
+    #[test]
+    fn test_parse_export() {
+        let intervals = parse_export(r#"[
+            {"id": 2, "start": "20260105T090000Z", "end": "20260105T100000Z", "tags": ["work"]},
+            {"id": 1, "start": "20260105T110000Z"}
+        ]"#).unwrap();
+        assert_eq!(intervals.len(), 2);
+        assert_eq!(intervals[1].end, None);
+    }
+
+    #[test]
+    fn test_focus_minutes_for_task() {
+        let mut task = TaskData::from_json(r#"{
+            "uuid": "5F2A3C1E", "status": "completed", "description": "Write report", "tags": ["writing"]
+        }"#).unwrap();
+        let intervals = vec![
+            interval("20260105T090000Z", Some("20260105T100000Z"), &["5f2a3c1e"]),
+            interval("20260105T093000Z", Some("20260105T103000Z"), &["Write report"]), // overlaps
+            interval("20260105T120000Z", Some("20260105T123000Z"), &["writing"]),
+            interval("20260104T120000Z", Some("20260104T130000Z"), &["5f2a3c1e"]), // before `from`
+        ];
+        let from = Some(at("20260105T000000Z"));
+        let to = at("20260105T180000Z");
+
+        assert_eq!(focus_minutes(&intervals, &task, false, from, to), 90.0);
+        assert_eq!(focus_minutes(&intervals, &task, true, from, to), 120.0);
+
+        task.uuid = "other".to_string();
+        task.description = "Other".to_string();
+        assert_eq!(focus_minutes(&intervals, &task, false, from, to), 0.0);
+    }
+
+    #[test]
+    fn test_focus_xp_is_capped() {
+        let config = TimewConfig::default();
+        assert_eq!(focus_xp(&config, 90.0), 15);
+        assert_eq!(focus_xp(&config, 600.0), 40);
+    }
+
+    #[test]
+    fn test_daily_focus() {
+        let intervals = vec![
+            interval("20260104T230000Z", Some("20260105T010000Z"), &[]), // half on the day
+            interval("20260105T090000Z", Some("20260105T103000Z"), &[]),
+            interval("20260105T103000Z", Some("20260105T110000Z"), &[]), // continues the block
+            interval("20260105T140000Z", None, &[]),                     // still running
+        ];
+        let focus = daily_focus(&intervals, "2026-01-05".parse().unwrap(), at("20260105T150000Z"));
+        assert_eq!(focus, DailyFocus { minutes: 240, longest_block: 120 });
+    }
+}